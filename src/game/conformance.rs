@@ -0,0 +1,276 @@
+//! # Game Conformance Module
+//!
+//! This module provides a suite of checks that any game implementation can
+//! opt into from its own tests, which verify that it upholds the contracts of
+//! the [`Implicit`], [`Codec`], [`Forward`], and [`Game`] interfaces over
+//! every state reachable in a selection of its variants. Each check fails
+//! with an explanation that names the first offending state it finds.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+
+use std::collections::HashSet;
+
+use crate::game::Codec;
+use crate::game::Forward;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::PlayerCount;
+use crate::game::State;
+use crate::game::Variable;
+use crate::solver::Game;
+use crate::solver::IUtility;
+use crate::solver::IntegerUtility;
+
+/* DEFINITIONS */
+
+/// The structure that the utility vectors of a game's terminal states are
+/// declared to have.
+#[derive(Clone, Copy, Debug)]
+pub enum Sum {
+    /// Utilities add up to zero, as in every [`crate::solver::ClassicGame`].
+    Zero,
+
+    /// Utilities add up to the same constant at every terminal state.
+    Constant(IUtility),
+
+    /// Utilities can add up to anything, as in games with more than two
+    /// players where some terminal states are ties and others are not.
+    Any,
+}
+
+/* SUITE */
+
+/// Runs every conformance check on each of the `variants` of `G` (where the
+/// empty string stands for the default variant), requiring the utilities of
+/// its terminal states to follow `sum`.
+///
+/// # Errors
+///
+/// Fails with an explanation of the first violated contract.
+pub fn suite<const N: PlayerCount, const B: usize, G>(
+    variants: &[&str],
+    sum: Sum,
+) -> Result<()>
+where
+    G: Information + Variable + Codec<B> + Implicit<B> + Forward<B>,
+    G: Game<N, B> + IntegerUtility<N, B>,
+{
+    default_state::<B, G>()?;
+    forward::<B, G>()?;
+    for &variant in variants {
+        let game = session::<G>(variant)?;
+        let states = reachable(&game);
+        codec(&game, &states)
+            .context(format!("Codec contract broken in '{variant}'."))?;
+
+        sinks(&game, &states).context(format!(
+            "Implicit contract broken in '{variant}'."
+        ))?;
+
+        turns::<N, B, G>(&game, &states)
+            .context(format!("Game contract broken in '{variant}'."))?;
+
+        utilities::<N, B, G>(&game, &states, sum).context(format!(
+            "Utility structure broken in '{variant}'."
+        ))?;
+    }
+    Ok(())
+}
+
+/* CHECKS */
+
+/// Checks that the default state encoding of `G` decodes to the starting
+/// state of its default variant.
+pub fn default_state<const B: usize, G>() -> Result<()>
+where
+    G: Information + Variable + Codec<B> + Implicit<B>,
+{
+    let info = G::info();
+    let game = session::<G>("")?;
+    let state = game
+        .decode(info.state_default.into())
+        .context("Failed to decode the default state encoding.")?;
+
+    if state != game.source() {
+        bail!(
+            "Default state '{}' does not decode to the starting state '{}'.",
+            info.state_default,
+            game.encode(game.source())?,
+        )
+    }
+    Ok(())
+}
+
+/// Checks that forwarding the default variant of `G` along the encodings of
+/// a path from its starting state to a terminal state leaves the last state
+/// of that path as the new starting state.
+pub fn forward<const B: usize, G>() -> Result<()>
+where
+    G: Information + Variable + Codec<B> + Implicit<B> + Forward<B>,
+{
+    let mut game = session::<G>("")?;
+    let mut path = vec![game.source()];
+    while let Some(&last) = path.last() {
+        if game.sink(last) {
+            break;
+        }
+
+        let children = game.adjacent(last);
+        path.push(children[children.len() / 2]);
+    }
+
+    let history = path
+        .iter()
+        .map(|&s| game.encode(s))
+        .collect::<Result<Vec<_>>>()?;
+
+    let last = *path.last().unwrap();
+    game.forward(history)
+        .context("Failed to forward along a valid history.")?;
+
+    if game.source() != last {
+        bail!(
+            "Forwarding to '{}' resulted in the starting state '{}'.",
+            game.encode(last)?,
+            game.encode(game.source())?,
+        )
+    }
+    Ok(())
+}
+
+/// Checks that `decode(encode(s)) == s` for all `states` of `game`.
+pub fn codec<const B: usize, G>(game: &G, states: &[State<B>]) -> Result<()>
+where
+    G: Codec<B>,
+{
+    for &state in states {
+        let encoding = game.encode(state)?;
+        let decoded = game
+            .decode(encoding.clone())
+            .context(format!("Failed to decode '{encoding}'."))?;
+
+        if decoded != state {
+            bail!(
+                "State '{encoding}' decodes to '{}'.",
+                game.encode(decoded)?
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Checks that each of `states` of `game` is a sink exactly when it has no
+/// transitions to other states.
+pub fn sinks<const B: usize, G>(game: &G, states: &[State<B>]) -> Result<()>
+where
+    G: Codec<B> + Implicit<B>,
+{
+    for &state in states {
+        let sink = game.sink(state);
+        let moves = game.adjacent(state).len();
+        if sink && moves > 0 {
+            bail!(
+                "Terminal state '{}' has {moves} transition(s).",
+                game.encode(state)?
+            )
+        } else if !sink && moves == 0 {
+            bail!(
+                "Non-terminal state '{}' has no transitions.",
+                game.encode(state)?
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the turn at each of `states` of `game` is that of one of its
+/// `N` players.
+pub fn turns<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    states: &[State<B>],
+) -> Result<()>
+where
+    G: Codec<B> + Game<N, B>,
+{
+    for &state in states {
+        let turn = game.turn(state);
+        if turn >= N {
+            bail!(
+                "State '{}' has turn {turn} in a {N}-player game.",
+                game.encode(state)?
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the utility vectors of the terminal states among `states` of
+/// `game` follow `sum`.
+pub fn utilities<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    states: &[State<B>],
+    sum: Sum,
+) -> Result<()>
+where
+    G: Codec<B> + Implicit<B> + IntegerUtility<N, B>,
+{
+    for &state in states
+        .iter()
+        .filter(|&&s| game.sink(s))
+    {
+        let utility = game.utility(state);
+        let total: IUtility = utility.iter().sum();
+        let expected = match sum {
+            Sum::Zero => 0,
+            Sum::Constant(c) => c,
+            Sum::Any => total,
+        };
+
+        if total != expected {
+            bail!(
+                "Terminal state '{}' has utility {utility:?}, which adds up \
+                to {total} instead of {expected}.",
+                game.encode(state)?
+            )
+        }
+    }
+    Ok(())
+}
+
+/* HELPERS */
+
+/// Returns a session of `G` under `variant`, or under its default variant if
+/// `variant` is empty.
+fn session<G>(variant: &str) -> Result<G>
+where
+    G: Information + Variable,
+{
+    let variant =
+        if variant.is_empty() { G::info().variant_default } else { variant };
+
+    G::variant(variant.into()).context(format!(
+        "Failed to initialize variant '{variant}'."
+    ))
+}
+
+/// Returns all states of `game` reachable from its starting state, without
+/// following transitions out of terminal states.
+fn reachable<const B: usize, G>(game: &G) -> Vec<State<B>>
+where
+    G: Implicit<B>,
+{
+    let mut seen = HashSet::new();
+    let mut stack = vec![game.source()];
+    let mut out = Vec::new();
+    while let Some(state) = stack.pop() {
+        if seen.insert(state) {
+            out.push(state);
+            if !game.sink(state) {
+                stack.extend(game.adjacent(state));
+            }
+        }
+    }
+    out
+}
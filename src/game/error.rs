@@ -16,19 +16,51 @@ pub enum GameError {
     /// `game` was not in a format the game could parse. Includes a message
     /// from the game implementation on exactly what went wrong. Note: `game`
     /// should be a valid argument to the `--target` parameter in the CLI.
-    VariantMalformed { game: &'static str, hint: String },
+    VariantMalformed {
+        /// Name of the game which rejected the variant.
+        game: &'static str,
+
+        /// Explanation of what is wrong with the variant.
+        hint: String,
+    },
 
     /// An error to indicate that the state string passed to the game with the
     /// name `game` was not in a format the game could parse. Includes a message
     /// from the game implementation on exactly what went wrong. Note: `game`
     /// should be a valid argument to the `--target` parameter in the CLI.    
-    StateMalformed { game: &'static str, hint: String },
+    StateMalformed {
+        /// Name of the game which rejected the state.
+        game: &'static str,
+
+        /// Explanation of what is wrong with the state.
+        hint: String,
+    },
 
     /// An error to indicate that a sequence of states in string form would
     /// be impossible to reproduce in real play. Includes a message from the
     /// game implementation on exactly what went wrong. Note: `target_name`
     /// should be a valid argument to the `--target` parameter in the CLI.
-    InvalidHistory { game: &'static str, hint: String },
+    InvalidHistory {
+        /// Name of the game which rejected the history.
+        game: &'static str,
+
+        /// Explanation of what is wrong with the history.
+        hint: String,
+    },
+
+    /// An error to indicate that a feature was requested from the game with
+    /// the name `game` which it does not provide. Includes the names of the
+    /// features the game does provide.
+    FeatureUnknown {
+        /// Name of the game from which the feature was requested.
+        game: &'static str,
+
+        /// Name of the requested feature.
+        feature: String,
+
+        /// Names of the features that the game provides.
+        available: Vec<&'static str>,
+    },
 }
 
 impl Error for GameError {}
@@ -59,6 +91,26 @@ impl fmt::Display for GameError {
                     found with 'nova info {game}'.",
                 )
             },
+            Self::FeatureUnknown {
+                game,
+                feature,
+                available,
+            } => {
+                if available.is_empty() {
+                    write!(
+                        f,
+                        "The game {game} does not provide any features, so \
+                        '{feature}' cannot be computed.",
+                    )
+                } else {
+                    write!(
+                        f,
+                        "The game {game} does not provide a feature named \
+                        '{feature}'. Available features are: {}.",
+                        available.join(", "),
+                    )
+                }
+            },
         }
     }
 }
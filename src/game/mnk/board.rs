@@ -0,0 +1,220 @@
+//! # MNK Board Geometry Module
+//!
+//! This module computes the parts of an m,n,k game variant that only depend on
+//! the shape of its board, which is any number of dimensions with any length.
+//! These are the lines that can be completed on it, and the symmetries that
+//! map lines to lines, both of which are computed once per session.
+//!
+//! Cells are indexed in row-major order, such that the last coordinate of a
+//! cell changes the fastest along consecutive indices.
+
+use std::collections::HashSet;
+
+use crate::game::mnk::Rules;
+
+/* DEFINITIONS */
+
+/// A line of K cells on a board, which is complete if all of its cells hold
+/// the same symbol.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Line {
+    /// Indices of the cells that make up the line, in order.
+    pub cells: Vec<usize>,
+
+    /// Indices of the cells just past either end of the line, which do not
+    /// include cells of the line itself (as can happen on a torus).
+    pub ends: Vec<usize>,
+}
+
+/* API */
+
+/// Returns the coordinates of the cell at `index` on a board with `sides`.
+pub fn coordinates(sides: &[usize], index: usize) -> Vec<usize> {
+    let mut coords = vec![0; sides.len()];
+    let mut rest = index;
+    for (c, &s) in coords.iter_mut().zip(sides).rev() {
+        *c = rest % s;
+        rest /= s;
+    }
+    coords
+}
+
+/// Returns the index of the cell at `coords` on a board with `sides`.
+pub fn index(sides: &[usize], coords: &[usize]) -> usize {
+    coords
+        .iter()
+        .zip(sides)
+        .fold(0, |acc, (&c, &s)| acc * s + c)
+}
+
+/// Returns every distinct line of `k` cells on a board with `sides` under
+/// `rules`, which can extend along any of the 3^d - 1 directions of a board
+/// with d dimensions (counting opposite directions once).
+pub fn lines(sides: &[usize], k: usize, rules: &Rules) -> Vec<Line> {
+    let cells: usize = sides.iter().product();
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for dir in directions(sides.len()) {
+        for start in 0..cells {
+            let from = coordinates(sides, start);
+            let cell = |d| step(sides, &from, &dir, d, rules.torus);
+            let Some(line) = (0..k as isize)
+                .map(cell)
+                .collect::<Option<Vec<usize>>>()
+            else {
+                continue;
+            };
+
+            let mut ends: Vec<usize> = [cell(-1), cell(k as isize)]
+                .into_iter()
+                .flatten()
+                .filter(|c| !line.contains(c))
+                .collect();
+
+            ends.sort();
+            ends.dedup();
+
+            // On a torus, the same cells can be reached from several starting
+            // cells (and directions) when lines wrap around the board.
+            let mut sorted = line.clone();
+            sorted.sort();
+            if seen.insert((sorted, ends.clone())) {
+                out.push(Line { cells: line, ends });
+            }
+        }
+    }
+    out
+}
+
+/// Returns permutations of the cells of a board with `sides` that generate the
+/// group of its symmetries under `rules`, where the contents of each cell `c`
+/// are moved to the cell at index `c` of the permutation. These are the
+/// reflections of each axis, the transpositions of axes of equal length, and
+/// (on a torus) the shifts of each axis. Under gravity, the first axis points
+/// down, so it is left unchanged.
+pub fn symmetries(sides: &[usize], rules: &Rules) -> Vec<Vec<usize>> {
+    let cells: usize = sides.iter().product();
+    let permutation = |f: &dyn Fn(&mut Vec<usize>)| -> Vec<usize> {
+        (0..cells)
+            .map(|c| {
+                let mut coords = coordinates(sides, c);
+                f(&mut coords);
+                index(sides, &coords)
+            })
+            .collect()
+    };
+
+    let first = if rules.gravity { 1 } else { 0 };
+    let mut out = Vec::new();
+    for a in first..sides.len() {
+        out.push(permutation(&|x| x[a] = sides[a] - 1 - x[a]));
+        if rules.torus {
+            out.push(permutation(&|x| x[a] = (x[a] + 1) % sides[a]));
+        }
+
+        for b in (a + 1)..sides.len() {
+            if sides[a] == sides[b] {
+                out.push(permutation(&|x| x.swap(a, b)));
+            }
+        }
+    }
+
+    out.retain(|p| {
+        p.iter()
+            .enumerate()
+            .any(|(i, &c)| i != c)
+    });
+
+    out
+}
+
+/* HELPERS */
+
+/// Returns the directions along `d` axes in which lines can extend, as offsets
+/// in each axis. Only one of each pair of opposite directions is included.
+fn directions(d: usize) -> Vec<Vec<isize>> {
+    (0..3usize.pow(d as u32))
+        .map(|mut i| {
+            (0..d)
+                .map(|_| {
+                    let offset = (i % 3) as isize - 1;
+                    i /= 3;
+                    offset
+                })
+                .collect::<Vec<isize>>()
+        })
+        .filter(|dir| dir.iter().find(|&&o| o != 0) == Some(&1))
+        .collect()
+}
+
+/// Returns the index of the cell `d` steps away from the cell at `from` in
+/// direction `dir` on a board with `sides`, or `None` if it falls off the
+/// board (which never happens on a torus).
+fn step(
+    sides: &[usize],
+    from: &[usize],
+    dir: &[isize],
+    d: isize,
+    torus: bool,
+) -> Option<usize> {
+    let mut coords = Vec::with_capacity(sides.len());
+    for ((&c, &s), &o) in from.iter().zip(sides).zip(dir) {
+        let x = c as isize + o * d;
+        let s = s as isize;
+        if torus {
+            coords.push(x.rem_euclid(s) as usize);
+        } else if (0..s).contains(&x) {
+            coords.push(x as usize);
+        } else {
+            return None;
+        }
+    }
+    Some(index(sides, &coords))
+}
+
+/* TESTS */
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn lines_extend_in_every_direction() {
+        let count = |sides: &[usize], k, torus| {
+            let rules = Rules {
+                torus,
+                ..Rules::default()
+            };
+            lines(sides, k, &rules).len()
+        };
+
+        assert_eq!(directions(3).len(), 13);
+        assert_eq!(count(&[3, 3], 3, false), 8);
+        assert_eq!(count(&[3, 4], 3, false), 3 * 2 + 4 + 2 * 2);
+        assert_eq!(count(&[4, 4, 4], 4, false), 76);
+        assert_eq!(count(&[3, 3], 3, true), 12);
+    }
+
+    #[test]
+    fn symmetries_are_permutations_of_cells() {
+        let sides = [2, 3, 3];
+        for rules in [
+            Rules::default(),
+            Rules {
+                gravity: true,
+                torus: true,
+                ..Rules::default()
+            },
+        ] {
+            for p in symmetries(&sides, &rules) {
+                let image: HashSet<_> = p.iter().collect();
+                assert_eq!(image.len(), 18);
+            }
+        }
+
+        assert_eq!(symmetries(&[3, 3], &Rules::default()).len(), 3);
+        assert_eq!(symmetries(&[1, 3], &Rules::default()).len(), 1);
+        assert_eq!(index(&sides, &coordinates(&sides, 13)), 13);
+    }
+}
@@ -1,75 +1,84 @@
 //! # MNK Game Feature Calculators
 //!
-//! Elements that compute attributes about m,n,k game states.
+//! Elements that compute attributes about m,n,k game states, on boards of any
+//! number of dimensions.
 
+use crate::game::Feature;
+use crate::game::Features;
 use crate::game::State;
 use crate::game::mnk::Board;
 use crate::game::mnk::Session;
 use crate::game::mnk::Symbol;
+use crate::game::mnk::board;
+use crate::solver::db;
+
+/* FEATURE DECLARATIONS */
+
+impl<const B: usize> Features<B> for Session<B> {
+    fn features() -> Vec<Feature<Self, B>> {
+        vec![
+            Feature {
+                name: "orbit_rep",
+                datatype: db::key_type::<B>(),
+                extract: |g, s| db::key(&g.canonical(s)),
+            },
+            Feature {
+                name: "fork_exists",
+                datatype: "INTEGER",
+                extract: |g, s| g.fork_exists(s).into(),
+            },
+            Feature {
+                name: "ply",
+                datatype: "INTEGER",
+                extract: |g, s| g.ply(s).into(),
+            },
+            Feature {
+                name: "center_control",
+                datatype: "INTEGER",
+                extract: |g, s| g.center_control(s).into(),
+            },
+            Feature {
+                name: "corner_count",
+                datatype: "INTEGER",
+                extract: |g, s| g.corner_count(s).into(),
+            },
+            Feature {
+                name: "edge_count",
+                datatype: "INTEGER",
+                extract: |g, s| g.edge_count(s).into(),
+            },
+        ]
+    }
+}
 
-impl Session {
+/* FEATURE CALCULATORS */
+
+impl<const B: usize> Session<B> {
     /// [GPT] Return true if the side to move has a “fork,” i.e. some move that
     /// creates two (or more) immediate winning threats. Here, true=1, false=-1.
-    pub fn fork_exists(&self, state: State) -> i64 {
+    /// Completing a line loses under the misère rule, so there are no winning
+    /// threats (and so no forks) in misère variants.
+    pub fn fork_exists(&self, state: State<B>) -> i64 {
+        if self.rules.misere {
+            return -1;
+        }
+
         let (turn, board) = self.decode_state(state);
-        let me = if turn == 1 { Symbol::X } else { Symbol::O };
-        let k = self.k;
-
-        // helper: does placing `me` at (i,j) win immediately?
-        let is_win = |b: &Board, i: usize, j: usize| -> bool {
-            // count in one direction + the opposite
-            let count_dir = |di: isize, dj: isize| {
-                let mut cnt = 0;
-                let mut r = i as isize + di;
-                let mut c = j as isize + dj;
-                while r >= 0
-                    && r < self.m as isize
-                    && c >= 0
-                    && c < self.n as isize
-                    && b[r as usize][c as usize] == me
-                {
-                    cnt += 1;
-                    r += di;
-                    c += dj;
-                }
-                cnt
-            };
-
-            for &((dx1, dy1), (dx2, dy2)) in &[
-                ((0, 1), (0, -1)),
-                ((1, 0), (-1, 0)),
-                ((1, 1), (-1, -1)),
-                ((1, -1), (-1, 1)),
-            ] {
-                let total = 1 + count_dir(dx1, dy1) + count_dir(dx2, dy2);
-                if total >= k {
-                    return true;
-                }
-            }
-            false
-        };
-
-        // for each empty cell, simulate your move and count winning replies
-        for i in 0..self.m {
-            for j in 0..self.n {
-                if board[i][j] != Symbol::B {
-                    continue;
-                }
-
-                let mut b2 = board;
-                b2[i][j] = me;
-
-                let mut threats = 0;
-                for ii in 0..self.m {
-                    for jj in 0..self.n {
-                        if b2[ii][jj] == Symbol::B && is_win(&b2, ii, jj) {
-                            threats += 1;
-                            if threats >= 2 {
-                                return 1;
-                            }
-                        }
-                    }
-                }
+        let me = self.symbol(turn);
+
+        // for each legal move, simulate it and count winning replies
+        for c in self.moves(&board) {
+            let mut b2 = board;
+            b2[c] = me;
+
+            let threats = self
+                .moves(&b2)
+                .into_iter()
+                .filter(|&r| self.completes(&b2, r, me))
+                .count();
+
+            if threats >= 2 {
+                return 1;
             }
         }
 
@@ -77,112 +86,95 @@ impl Session {
     }
 
     /// [GPT] Number of moves already played (non‐blank cells)
-    pub fn ply(&self, state: State) -> i64 {
+    pub fn ply(&self, state: State<B>) -> i64 {
         let (_turn, board) = self.decode_state(state);
-        let mut count = 0;
-        (0..self.m).for_each(|i| {
-            for j in 0..self.n {
-                if board[i][j] != Symbol::B {
-                    count += 1;
-                }
-            }
-        });
-        count
+        board[..self.size()]
+            .iter()
+            .filter(|&&s| s != Symbol::B)
+            .count() as i64
     }
 
     /// [GPT]
     /// +1 if the center cell is occupied by the side to move,
     /// -1 if occupied by opponent, 0 if empty or no exact center.
-    pub fn center_control(&self, state: State) -> i64 {
-        let (turn, board) = self.decode_state(state);
-        let me = if turn == 1 { Symbol::X } else { Symbol::O };
-        let opp = if me == Symbol::X { Symbol::O } else { Symbol::X };
-
-        // only defined when both dims are odd
-        if self.m % 2 == 1 && self.n % 2 == 1 {
-            let ci = self.m / 2;
-            let cj = self.n / 2;
-            return match board[ci][cj] {
-                b if b == me => 1,
-                b if b == opp => -1,
-                _ => 0,
-            };
+    pub fn center_control(&self, state: State<B>) -> i64 {
+        // only defined when all dims are odd
+        if self
+            .sides
+            .iter()
+            .all(|s| s % 2 == 1)
+        {
+            let center: Vec<usize> = self
+                .sides
+                .iter()
+                .map(|s| s / 2)
+                .collect();
+
+            let cell = board::index(&self.sides, &center);
+            return self.control(state, [cell]);
         }
         0
     }
 
     /// [GPT]
     /// Returns (your corners) – (opponent corners)
-    /// corners = (0,0), (0,n-1), (m-1,0), (m-1,n-1)
-    pub fn corner_count(&self, state: State) -> i64 {
-        let (turn, board) = self.decode_state(state);
-        let me = if turn == 1 { Symbol::X } else { Symbol::O };
-        let opp = if me == Symbol::X { Symbol::O } else { Symbol::X };
-
-        let mut diff = 0;
-        let last_row = self.m - 1;
-        let last_col = self.n - 1;
-        let corners = [
-            (0, 0),
-            (0, last_col),
-            (last_row, 0),
-            (last_row, last_col),
-        ];
-
-        for &(i, j) in &corners {
-            diff += match board[i][j] {
-                b if b == me => 1,
-                b if b == opp => -1,
-                _ => 0,
-            };
-        }
-        diff
+    /// corners = cells at either end of every dimension
+    pub fn corner_count(&self, state: State<B>) -> i64 {
+        let corners = (0..self.size())
+            .filter(|&c| self.boundaries(c) == self.sides.len());
+        self.control(state, corners)
     }
 
     /// [GPT]
     /// Returns (your edges) – (opponent edges)
-    /// edges = border cells excluding the four corners
-    pub fn edge_count(&self, state: State) -> i64 {
+    /// edges = border cells excluding the corners
+    pub fn edge_count(&self, state: State<B>) -> i64 {
+        let edges = (0..self.size()).filter(|&c| {
+            let b = self.boundaries(c);
+            b > 0 && b < self.sides.len()
+        });
+        self.control(state, edges)
+    }
+
+    /* HELPERS */
+
+    /// Returns the number of `cells` of `state` that hold the symbol of the
+    /// side to move, minus those that hold the symbol of any opponent.
+    fn control(
+        &self,
+        state: State<B>,
+        cells: impl IntoIterator<Item = usize>,
+    ) -> i64 {
         let (turn, board) = self.decode_state(state);
-        let me = if turn == 1 { Symbol::X } else { Symbol::O };
-        let opp = if me == Symbol::X { Symbol::O } else { Symbol::X };
-
-        let mut diff = 0;
-        let last_row = self.m - 1;
-        let last_col = self.n - 1;
-
-        // top & bottom row (excluding corners)
-        if self.n > 2 {
-            for j in 1..last_col {
-                diff += match board[0][j] {
-                    b if b == me => 1,
-                    b if b == opp => -1,
-                    _ => 0,
-                };
-                diff += match board[last_row][j] {
-                    b if b == me => 1,
-                    b if b == opp => -1,
-                    _ => 0,
-                };
-            }
-        }
+        let me = self.symbol(turn);
+        cells
+            .into_iter()
+            .map(|c| match board[c] {
+                b if b == me => 1,
+                Symbol::B => 0,
+                _ => -1,
+            })
+            .sum()
+    }
 
-        // left & right column (excluding corners)
-        if self.m > 2 {
-            (1..last_row).for_each(|i| {
-                diff += match board[i][0] {
-                    b if b == me => 1,
-                    b if b == opp => -1,
-                    _ => 0,
-                };
-                diff += match board[i][last_col] {
-                    b if b == me => 1,
-                    b if b == opp => -1,
-                    _ => 0,
-                };
-            });
-        }
+    /// Returns true if placing `sym` on `cell` of `board` completes a line of
+    /// `sym` that goes through `cell`.
+    fn completes(&self, board: &Board, cell: usize, sym: Symbol) -> bool {
+        let mut next = *board;
+        next[cell] = sym;
+        self.lines
+            .iter()
+            .filter(|line| line.cells.contains(&cell))
+            .any(|line| self.complete(&next, line, sym))
+    }
 
-        diff
+    /// Returns the number of dimensions along which `cell` is at either end
+    /// of the board.
+    fn boundaries(&self, cell: usize) -> usize {
+        board::coordinates(&self.sides, cell)
+            .iter()
+            .zip(&self.sides)
+            .filter(|&(&x, &s)| x == 0 || x == s - 1)
+            .count()
     }
 }
@@ -3,6 +3,15 @@
 //! The m,n,k game is a generalization of Tic-Tac-Toe that is also acyclic. It
 //! allows for play on an m-by-n board, where k symbols in a row belonging to
 //! either of the two players results in an immediate win for that player.
+//! Variants can also have more than two players, each with their own symbol,
+//! in which case completing a line wins and every other player loses.
+//! Boards can also have more than two dimensions, as in Qubic (which is played
+//! on a 4x4x4 cube), in which case lines can extend along any direction.
+//!
+//! Variants can also change the rules of the game in a few common ways (see
+//! [`Rules`]), which makes it possible to study games like Toe-Tac-Tic (where
+//! completing a line loses), Connect Four (where symbols fall to the bottom of
+//! their column), or play on a torus within this same module.
 
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -17,17 +26,21 @@ use rusqlite::Error::QueryReturnedNoRows;
 use rusqlite::Statement;
 use rusqlite::Transaction;
 use rusqlite::params_from_iter;
+use rusqlite::types::Value;
 
 use crate::game::Codec;
+use crate::game::DEFAULT_STATE_BYTES;
 use crate::game::Forward;
 use crate::game::GameData;
 use crate::game::Implicit;
 use crate::game::Information;
+use crate::game::Parameters;
 use crate::game::Player;
 use crate::game::PlayerCount;
 use crate::game::State;
 use crate::game::Variable;
 use crate::game::Variant;
+use crate::game::mnk::board::Line;
 use crate::game::mnk::states::*;
 use crate::game::mnk::variants::*;
 use crate::interface::IOMode;
@@ -37,162 +50,241 @@ use crate::solver::Queries;
 use crate::solver::SUtility;
 use crate::solver::SimpleUtility;
 use crate::solver::Solution;
-use crate::solver::algorithm::acyclic;
+use crate::solver::Tabular;
+use crate::solver::db;
 use crate::solver::db::Schema;
 
 /* SUBMODULES */
 
+mod board;
 mod states;
 mod variants;
 mod features;
 
 /* DEFINITIONS */
 
-type Board = [[Symbol; MAX_BOARD_SIDE]; MAX_BOARD_SIDE];
+/// Number of bytes in the state encodings of variants whose boards are too
+/// large to encode in [`DEFAULT_STATE_BYTES`].
+pub const WIDE_STATE_BYTES: usize = 32;
+
+/// Maximum number of cells on a board, which is the number of 2-bit cells that
+/// fit next to the turn bit in the widest state encoding of a 2-player game.
+const MAX_CELLS: usize = (8 * WIDE_STATE_BYTES - 1) / 2;
+
+/// Maximum number of dimensions of a board.
+const MAX_DIMENSIONS: usize = 6;
 
-const MAX_BOARD_SIDE: usize = 10;
+/// Characters of the symbols that can be placed on a board, indexed by their
+/// encoding. Players place them in this order, starting from 'X'.
+const SYMBOLS: &str = "_XOABCDEFGHIJKLMN";
 
+type Board = [Symbol; MAX_CELLS];
+
+/// The contents of a cell, which is either blank or the symbol of a player,
+/// identified by its encoding.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-enum Symbol {
-    B = 0,
-    X = 1,
-    O = 2,
-}
+struct Symbol(u8);
 
 /* GAME DATA */
 
 const NAME: &str = "mnk";
 const AUTHORS: &str = "Max Fierro <maxfierro@berkeley.edu>";
-const ABOUT: &str = "Two players take turns placing Xs and Os on a square grid \
-of dimensions MxN. The first player to complete K of their own symbol in a \
-row, which may be diagonally, horizontally, or vertically, wins the game. \
-Skipping moves is not allwed; players must place a symbol on their turn.";
+const ABOUT: &str = "Players take turns placing their own symbol on an empty \
+cell of a grid, which is an MxN rectangle on which two players place Xs and \
+Os by default. The first player to complete K of their own symbol in a row, \
+which may be diagonally, horizontally, or vertically, wins the game. Skipping \
+moves is not allowed; players must place a symbol on their turn. \
+Boards can also have more than two dimensions (such as a 4x4x4 cube), where \
+lines can extend along any direction. Variants can have up to 16 players, \
+where those after X and O place the symbols A, B, C, and so on, and every \
+player loses when another completes a line. Variants can also make completing \
+K in a row lose instead (misere), only count lines of exactly K symbols \
+(exact), make symbols fall to the lowest empty cell of their column (gravity), \
+or make lines wrap around the edges of the board (torus).";
+
+/* API */
+
+/// Returns the number of bytes in the state encodings of sessions over
+/// `variant`, which is the smallest of [`DEFAULT_STATE_BYTES`] and
+/// [`WIDE_STATE_BYTES`] that fits the variant's board.
+pub fn state_bytes(variant: &str) -> usize {
+    if encoding_bits(variant).is_some_and(|b| b <= 8 * DEFAULT_STATE_BYTES) {
+        DEFAULT_STATE_BYTES
+    } else {
+        WIDE_STATE_BYTES
+    }
+}
 
 /* GAME IMPLEMENTATION */
 
-pub struct Session {
+/// A session of a variant of the m,n,k-game, whose states are encoded in `B`
+/// bytes.
+pub struct Session<const B: usize = DEFAULT_STATE_BYTES> {
+    variant: Variant,
     schema: Schema,
-    start: State,
-    m: usize,
-    n: usize,
-    k: usize,
+    start: State<B>,
+    players: PlayerCount,
+    sides: Vec<usize>,
+    rules: Rules,
+    lines: Vec<Line>,
+    symmetries: Vec<Vec<usize>>,
 }
 
-impl Session {
-    pub fn new(variant: Option<Variant>) -> Result<Self> {
-        if let Some(v) = variant {
-            Self::variant(v)
-        } else {
-            Ok(Self::default())
-        }
-    }
+/// Changes to the standard rules of the m,n,k-game enabled by a variant.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+struct Rules {
+    /// Completing K symbols in a row loses the game instead of winning it.
+    misere: bool,
 
-    pub fn solve(&mut self, mode: IOMode) -> Result<()> {
-        acyclic::solve::<2, 8, _>(self, mode)
-    }
+    /// Only lines of exactly K symbols are complete, so longer ones are not.
+    exact: bool,
+
+    /// Symbols are placed on the lowest empty cell of a column, with the last
+    /// layer along the first dimension of the board being the bottom.
+    gravity: bool,
 
+    /// Lines wrap around the edges of the board, which makes its opposite
+    /// sides adjacent.
+    torus: bool,
+}
+
+impl<const B: usize> Session<B> {
     /* INTERNAL API */
 
-    fn encode_state(&self, turn: Player, board: &Board) -> State {
-        let mut state = BitArray::<_, Msb0>::ZERO;
-        (0..self.m).for_each(|i| {
-            for j in 0..self.n {
-                let cell = i * self.n + j;
-                let start = 1 + 2 * cell;
-                state[start..start + 2].store_be(board[i][j] as u8);
-            }
-        });
+    /// Returns the number of cells on the board.
+    fn size(&self) -> usize {
+        self.sides.iter().product()
+    }
+
+    fn encode_state(&self, turn: Player, board: &Board) -> State<B> {
+        let mut state = BitArray::<[u8; B], Msb0>::ZERO;
+        let (t, w) = (turn_bits(self.players), cell_bits(self.players));
+        for (cell, &sym) in board[..self.size()]
+            .iter()
+            .enumerate()
+        {
+            let start = t + w * cell;
+            state[start..start + w].store_be(sym.0);
+        }
 
-        state[..1].store_be(turn);
+        state[..t].store_be(turn);
         state.data
     }
 
-    fn decode_state(&self, state: State) -> (Player, Board) {
-        let state = BitArray::<[u8; 8], Msb0>::from(state);
-        let turn = state[..1].load_be::<Player>();
-        let mut board = [[Symbol::B; MAX_BOARD_SIDE]; MAX_BOARD_SIDE];
-        (0..self.m).for_each(|i| {
-            for j in 0..self.n {
-                let start = 1 + 2 * (i * self.n + j);
-                let code = state[start..start + 2].load_be::<u8>();
-                board[i][j] = Symbol::from(code);
-            }
-        });
+    fn decode_state(&self, state: State<B>) -> (Player, Board) {
+        let state = BitArray::<[u8; B], Msb0>::from(state);
+        let (t, w) = (turn_bits(self.players), cell_bits(self.players));
+        let turn = state[..t].load_be::<Player>();
+        let mut board = [Symbol::B; MAX_CELLS];
+        for (cell, sym) in board[..self.size()]
+            .iter_mut()
+            .enumerate()
+        {
+            let start = t + w * cell;
+            *sym = Symbol(state[start..start + w].load_be::<u8>());
+        }
 
         (turn, board)
     }
 
+    /// Returns the symbol placed by the player whose turn it is at `turn`.
+    fn symbol(&self, turn: Player) -> Symbol {
+        if turn == 0 {
+            Symbol(self.players as u8)
+        } else {
+            Symbol(turn as u8)
+        }
+    }
+
+    /// Returns the player who places `sym`, which must not be blank.
+    fn player(&self, sym: Symbol) -> Player {
+        sym.0 as Player % self.players
+    }
+
+    /// Returns true if `sym` has completed a line on `board`.
+    #[cfg(test)]
     fn win(&self, board: &Board, sym: Symbol) -> bool {
-        (0..self.m).any(|i| {
-            (0..=(self.n - self.k))
-                .any(|j| (0..self.k).all(|d| board[i][j + d] == sym))
-        }) || (0..self.n).any(|j| {
-            (0..=(self.m - self.k))
-                .any(|i| (0..self.k).all(|d| board[i + d][j] == sym))
-        }) || (0..=(self.m - self.k)).any(|i| {
-            (0..=(self.n - self.k))
-                .any(|j| (0..self.k).all(|d| board[i + d][j + d] == sym))
-        }) || ((self.k - 1)..self.m).any(|i| {
-            (0..=(self.n - self.k))
-                .any(|j| (0..self.k).all(|d| board[i - d][j + d] == sym))
-        })
+        self.lines
+            .iter()
+            .any(|line| self.complete(board, line, sym))
+    }
+
+    /// Returns the symbol of the first complete line found on `board`, if any.
+    fn winner(&self, board: &Board) -> Option<Symbol> {
+        self.lines
+            .iter()
+            .map(|line| board[line.cells[0]])
+            .zip(&self.lines)
+            .find(|&(sym, line)| {
+                sym != Symbol::B && self.complete(board, line, sym)
+            })
+            .map(|(sym, _)| sym)
+    }
+
+    /// Returns true if every cell of `line` holds `sym` on `board`, and (under
+    /// the exact rule) the cells just past its ends do not.
+    fn complete(&self, board: &Board, line: &Line, sym: Symbol) -> bool {
+        line.cells
+            .iter()
+            .all(|&c| board[c] == sym)
+            && (!self.rules.exact
+                || line
+                    .ends
+                    .iter()
+                    .all(|&c| board[c] != sym))
+    }
+
+    /// Returns the cells on which a symbol can be placed on `board`.
+    fn moves(&self, board: &Board) -> Vec<usize> {
+        let size = self.size();
+        let below = size / self.sides[0];
+        (0..size)
+            .filter(|&c| board[c] == Symbol::B)
+            .filter(|&c| {
+                !self.rules.gravity
+                    || c + below >= size
+                    || board[c + below] != Symbol::B
+            })
+            .collect()
     }
 
     fn draw(&self, board: &Board) -> bool {
-        (0..self.m).all(|i| (0..self.n).all(|j| board[i][j] != Symbol::B))
+        board[..self.size()]
+            .iter()
+            .all(|&s| s != Symbol::B)
     }
 
-    fn canonical(&self, state: State) -> State {
+    fn canonical(&self, state: State<B>) -> State<B> {
         *self
             .orbit(state)
             .iter()
-            .min_by_key(|&s| {
-                BitArray::<[u8; 8], Msb0>::from(*s).load_be::<u64>()
-            })
+            .min()
             .unwrap()
     }
 
-    fn orbit(&self, state: State) -> Vec<State> {
+    /// Returns the states obtained by applying every symmetry of the board to
+    /// the board of `state`.
+    fn orbit(&self, state: State<B>) -> Vec<State<B>> {
         let (turn, board0) = self.decode_state(state);
-        let m = self.m;
-        let n = self.n;
-
-        fn rotate(b: &Board, m: usize, n: usize) -> (Board, usize, usize) {
-            let mut nb = [[Symbol::B; MAX_BOARD_SIDE]; MAX_BOARD_SIDE];
-            (0..m).for_each(|i| {
-                (0..n).for_each(|j| {
-                    nb[j][m - 1 - i] = b[i][j];
-                });
-            });
-            (nb, n, m)
-        }
-
-        fn reflect(b: &Board, m: usize, n: usize) -> (Board, usize, usize) {
-            let mut nb = [[Symbol::B; MAX_BOARD_SIDE]; MAX_BOARD_SIDE];
-            (0..m).for_each(|i| {
-                (0..n).for_each(|j| {
-                    nb[i][n - 1 - j] = b[i][j];
-                })
-            });
-            (nb, m, n)
-        }
-
+        let size = self.size();
         let mut seen = HashSet::new();
         let mut queue = VecDeque::new();
 
-        seen.insert((board0, m, n));
-        queue.push_back((board0, m, n));
-        while let Some((b, p, q)) = queue.pop_front() {
-            for (nb, np, nq) in [rotate(&b, p, q), reflect(&b, p, q)] {
-                if np == m && nq == n && seen.insert((nb, np, nq)) {
-                    queue.push_back((nb, np, nq));
+        seen.insert(board0);
+        queue.push_back(board0);
+        while let Some(b) = queue.pop_front() {
+            for p in self.symmetries.iter() {
+                let mut nb = [Symbol::B; MAX_CELLS];
+                (0..size).for_each(|c| nb[p[c]] = b[c]);
+                if seen.insert(nb) {
+                    queue.push_back(nb);
                 }
             }
         }
 
-        let mut res: Vec<State> = seen
+        let mut res: Vec<State<B>> = seen
             .into_iter()
-            .map(|(b, _, _)| self.encode_state(turn, &b))
+            .map(|b| self.encode_state(turn, &b))
             .collect();
 
         res.sort();
@@ -203,14 +295,14 @@ impl Session {
 
 /* IMPLEMENTATIONS */
 
-impl Default for Session {
+impl<const B: usize> Default for Session<B> {
     fn default() -> Self {
         parse_variant(VARIANT_DEFAULT.to_owned())
             .expect("Failed to parse default variant.")
     }
 }
 
-impl Information for Session {
+impl<const B: usize> Information for Session<B> {
     fn info() -> GameData {
         GameData {
             name: NAME,
@@ -228,90 +320,99 @@ impl Information for Session {
     }
 }
 
-impl Variable for Session {
+impl<const B: usize> Parameters for Session<B> {
+    fn variant_string(&self) -> &str {
+        &self.variant
+    }
+
+    fn player_count(&self) -> PlayerCount {
+        self.players
+    }
+}
+
+impl<const B: usize> Variable for Session<B> {
     fn variant(variant: Variant) -> Result<Self> {
         parse_variant(variant).context("Malformed game variant.")
     }
 }
 
-impl Implicit for Session {
-    fn adjacent(&self, state: State) -> Vec<State> {
-        let (turn, board) = self.decode_state(state);
-        let sym = if turn == 1 { Symbol::X } else { Symbol::O };
-        let next = 1 - turn;
-        let mut out = Vec::new();
-        for i in 0..self.m {
-            for j in 0..self.n {
-                if board[i][j] == Symbol::B {
-                    let mut nb = board;
-                    nb[i][j] = sym;
-                    out.push(self.encode_state(next, &nb));
-                }
-            }
+impl<const B: usize> Implicit<B> for Session<B> {
+    fn adjacent(&self, state: State<B>) -> Vec<State<B>> {
+        if self.sink(state) {
+            return Vec::new();
         }
-        out
+
+        let (turn, board) = self.decode_state(state);
+        let sym = self.symbol(turn);
+        let next = (turn + 1) % self.players;
+        self.moves(&board)
+            .into_iter()
+            .map(|c| {
+                let mut nb = board;
+                nb[c] = sym;
+                self.encode_state(next, &nb)
+            })
+            .collect()
     }
 
-    fn source(&self) -> State {
-        let board = [[Symbol::B; MAX_BOARD_SIDE]; MAX_BOARD_SIDE];
-        self.encode_state(1, &board)
+    fn source(&self) -> State<B> {
+        self.start
     }
 
-    fn sink(&self, state: State) -> bool {
+    fn sink(&self, state: State<B>) -> bool {
         let (_, board) = self.decode_state(state);
-        self.win(&board, Symbol::O)
-            || self.win(&board, Symbol::X)
-            || self.draw(&board)
+        self.winner(&board).is_some() || self.draw(&board)
     }
 }
 
-impl Codec for Session {
-    fn decode(&self, string: String) -> Result<State> {
+impl<const B: usize> Codec<B> for Session<B> {
+    fn decode(&self, string: String) -> Result<State<B>> {
         decode_state_string(self, string)
     }
 
-    fn encode(&self, state: State) -> Result<String> {
+    fn encode(&self, state: State<B>) -> Result<String> {
         let (_turn, board) = self.decode_state(state);
         encode_state_string(self, &board)
     }
 }
 
-impl Forward for Session {
-    fn set_verified_start(&mut self, state: State) {
+impl<const B: usize> Forward<B> for Session<B> {
+    fn set_verified_start(&mut self, state: State<B>) {
         self.start = state;
     }
 }
 
-impl Game<2> for Session {
-    fn turn(&self, state: State) -> Player {
+impl<const N: PlayerCount, const B: usize> Game<N, B> for Session<B> {
+    fn turn(&self, state: State<B>) -> Player {
         let (turn, _) = self.decode_state(state);
         turn
     }
 }
 
-impl SimpleUtility<2> for Session {
-    fn utility(&self, state: State) -> [SUtility; 2] {
+impl<const N: PlayerCount, const B: usize> SimpleUtility<N, B> for Session<B> {
+    fn utility(&self, state: State<B>) -> [SUtility; N] {
         let (_turn, board) = self.decode_state(state);
-        let mut result = [SUtility::Tie; 2];
-        if !self.draw(&board) {
-            let x_wins = self.win(&board, Symbol::X);
-            let o_wins = self.win(&board, Symbol::O);
-            if x_wins && !o_wins {
-                result[1] = SUtility::Win;
-                result[0] = SUtility::Lose;
-            } else if o_wins && !x_wins {
-                result[0] = SUtility::Win;
-                result[1] = SUtility::Lose;
-            } else {
-                panic!()
-            }
+        let (completer, others) = if self.rules.misere {
+            (SUtility::Lose, SUtility::Win)
+        } else {
+            (SUtility::Win, SUtility::Lose)
+        };
+
+        // A full board can still have a complete line on it, so lines must be
+        // checked before declaring a tie.
+        match self.winner(&board) {
+            Some(sym) => {
+                let mut utility = [others; N];
+                utility[self.player(sym)] = completer;
+                utility
+            },
+            None if self.draw(&board) => [SUtility::Tie; N],
+            None => panic!(),
         }
-
-        result
     }
 }
 
-impl<const N: PlayerCount> Persistent<N> for Session {
+impl<const N: PlayerCount, const B: usize> Persistent<N, B> for Session<B> {
     fn prepare(
         &mut self,
         tx: &mut Transaction,
@@ -340,34 +441,32 @@ impl<const N: PlayerCount> Persistent<N> for Session {
     fn insert(
         &mut self,
         stmt: &mut Statement,
-        state: &State,
+        state: &State<B>,
         info: &Solution<N>,
     ) -> Result<()> {
-        stmt.execute(params_from_iter(
-            [
-                i64::from_be_bytes(*state),
-                info.remoteness as i64,
-                info.player as i64,
-                i64::from_be_bytes(self.canonical(*state)),
-                self.fork_exists(*state),
-                self.ply(*state),
-                self.center_control(*state),
-                self.corner_count(*state),
-                self.edge_count(*state),
-            ]
-            .iter()
-            .chain(info.utility.iter()),
-        ))?;
+        let mut row = vec![
+            db::key(state),
+            Value::Integer(info.remoteness as i64),
+            Value::Integer(info.player as i64),
+        ];
+
+        row.extend(
+            info.utility
+                .iter()
+                .map(|&u| Value::Integer(u)),
+        );
+
+        stmt.execute(params_from_iter(row))?;
         Ok(())
     }
 
     fn select(
         &mut self,
         stmt: &mut Statement,
-        state: &State,
+        state: &State<B>,
     ) -> Result<Option<Solution<N>>> {
         let start = self.schema.utility_index();
-        let row = stmt.query_row([i64::from_be_bytes(*state)], |row| {
+        let row = stmt.query_row([db::key(state)], |row| {
             let mut utility: [i64; N] = [0; N];
             for (i, item) in utility.iter_mut().enumerate() {
                 *item = row.get(start + i)?;
@@ -388,15 +487,216 @@ impl<const N: PlayerCount> Persistent<N> for Session {
     }
 }
 
+impl<const B: usize> Tabular for Session<B> {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
 /* UTILITY IMPLEMENTATIONS */
 
-impl From<u8> for Symbol {
-    fn from(value: u8) -> Self {
-        match value {
-            0 => Symbol::B,
-            1 => Symbol::X,
-            2 => Symbol::O,
-            other => panic!("Invalid symbol encoding: {}", other),
-        }
+impl Symbol {
+    /// The contents of an empty cell.
+    const B: Symbol = Symbol(0);
+
+    /// The symbol of the player who moves first.
+    #[cfg(test)]
+    const X: Symbol = Symbol(1);
+
+    /// The symbol of the player who moves second.
+    #[cfg(test)]
+    const O: Symbol = Symbol(2);
+
+    /// Returns the symbol represented by `c`, if any.
+    fn parse(c: char) -> Option<Symbol> {
+        SYMBOLS
+            .find(c)
+            .map(|i| Symbol(i as u8))
+    }
+
+    /// Returns the character that represents this symbol.
+    fn char(self) -> char {
+        SYMBOLS
+            .chars()
+            .nth(self.0 as usize)
+            .expect("Invalid symbol encoding.")
+    }
+}
+
+/* HELPERS */
+
+/// Returns the number of bits used to encode turns in a game of `players`.
+fn turn_bits(players: PlayerCount) -> usize {
+    (usize::BITS - (players - 1).leading_zeros()) as usize
+}
+
+/// Returns the number of bits used to encode each cell in a game of `players`,
+/// which can hold any of their symbols or be blank.
+fn cell_bits(players: PlayerCount) -> usize {
+    turn_bits(players + 1)
+}
+
+/* TESTS */
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::game::conformance;
+    use crate::game::conformance::Sum;
+
+    #[test]
+    fn session_conforms_to_game_contracts() -> Result<()> {
+        conformance::suite::<2, 8, Session>(
+            &["", "2-3-2", "3-2-2"],
+            Sum::Zero,
+        )?;
+        conformance::suite::<2, 8, Session>(
+            &[
+                "3-3-3-misere",
+                "2-4-2-exact",
+                "2-4-2-gravity",
+                "2-3-2-torus",
+                "2-2-2-2",
+            ],
+            Sum::Zero,
+        )?;
+
+        conformance::suite::<2, WIDE_STATE_BYTES, Session<WIDE_STATE_BYTES>>(
+            &["2-2-2-2-gravity"],
+            Sum::Zero,
+        )
+    }
+
+    #[test]
+    fn session_conforms_to_game_contracts_with_more_players() -> Result<()> {
+        conformance::suite::<3, 8, Session>(
+            &[
+                "2-3-3-p3",
+                "2-3-2-p3-misere",
+                "2-2-2-2-p3-gravity",
+            ],
+            Sum::Any,
+        )?;
+
+        conformance::suite::<4, 8, Session>(&["2-3-2-p4"], Sum::Any)
+    }
+
+    #[test]
+    fn players_take_turns_placing_their_own_symbols() -> Result<()> {
+        let game = <Session>::variant("3-3-3-p3".into())?;
+        let state = game.decode("[[X, O, _], [X, O, _], [A, _, _]]".into())?;
+        let (turn, _) = game.decode_state(state);
+        assert_eq!(game.symbol(turn), Symbol(3));
+
+        let next = game.decode("[[X, O, _], [X, O, _], [A, _, A]]".into())?;
+        assert!(
+            game.adjacent(state)
+                .contains(&next)
+        );
+        assert_eq!(game.decode_state(next).0, 1);
+        assert!(!game.sink(next));
+
+        let win = game.decode("[[X, O, _], [X, O, A], [X, A, _]]".into())?;
+        assert!(game.sink(win));
+        assert!(matches!(
+            <Session as SimpleUtility<3>>::utility(&game, win),
+            [SUtility::Lose, SUtility::Win, SUtility::Lose]
+        ));
+
+        let win = game.decode("[[X, O, A], [X, O, A], [_, X, A]]".into())?;
+        assert!(matches!(
+            <Session as SimpleUtility<3>>::utility(&game, win),
+            [SUtility::Win, SUtility::Lose, SUtility::Lose]
+        ));
+
+        assert_eq!(encoding_bits("3-3-3"), Some(2 * 9 + 1));
+        assert_eq!(encoding_bits("3-3-3-p3"), Some(2 * 9 + 2));
+        assert_eq!(encoding_bits("3-3-3-p4"), Some(3 * 9 + 2));
+        assert_eq!(encoding_bits("2-2-2-p16"), Some(5 * 4 + 4));
+        Ok(())
+    }
+
+    #[test]
+    fn boards_can_have_more_than_two_dimensions() -> Result<()> {
+        assert_eq!(state_bytes(VARIANT_DEFAULT), DEFAULT_STATE_BYTES);
+        assert_eq!(state_bytes("4-4-4-4"), WIDE_STATE_BYTES);
+
+        let qubic = Session::<WIDE_STATE_BYTES>::variant("4-4-4-4".into())?;
+        let layer = |x: usize| {
+            let rows: Vec<String> = (0..4)
+                .map(|i| {
+                    let cells: Vec<&str> = (0..4)
+                        .map(|j| if i == x && j == x { "X" } else { "_" })
+                        .collect();
+                    format!("[{}]", cells.join(", "))
+                })
+                .collect();
+            format!("[{}]", rows.join(", "))
+        };
+
+        let diagonal: Vec<String> = (0..4).map(layer).collect();
+        let encoding = format!("[{}]", diagonal.join(", "));
+        let state = qubic.decode(encoding.clone())?;
+        let (_, board) = qubic.decode_state(state);
+        assert!(qubic.win(&board, Symbol::X));
+        assert_eq!(qubic.encode(state)?, encoding);
+        assert_eq!(qubic.lines.len(), 76);
+        assert_eq!(qubic.orbit(state).len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn rules_change_complete_lines_and_moves() -> Result<()> {
+        let board = |s: &Session, b: &str| -> Result<Board> {
+            Ok(s.decode_state(s.decode(b.into())?)
+                .1)
+        };
+
+        let exact = <Session>::variant("3-4-2-exact".into())?;
+        let b = board(
+            &exact,
+            "[[X, X, X, _], [_, _, _, _], [O, O, _, _]]",
+        )?;
+        assert!(!exact.win(&b, Symbol::X));
+        assert!(exact.win(&b, Symbol::O));
+
+        let torus = <Session>::variant("3-3-3-torus".into())?;
+        let b = board(&torus, "[[_, X, _], [X, _, _], [_, _, X]]")?;
+        assert!(torus.win(&b, Symbol::X));
+        assert!(!<Session>::default().win(&b, Symbol::X));
+
+        let misere = <Session>::variant("3-3-3-misere".into())?;
+        let b = "[[X, X, X], [O, O, _], [_, _, _]]";
+        let state = misere.decode(b.into())?;
+        assert!(matches!(
+            misere.utility(state),
+            [SUtility::Win, SUtility::Lose]
+        ));
+
+        let gravity = <Session>::variant("3-3-3-gravity".into())?;
+        let b = board(&gravity, "[[_, _, _], [_, _, _], [X, _, _]]")?;
+        assert_eq!(gravity.moves(&b), vec![3, 7, 8]);
+        assert_eq!(
+            gravity
+                .orbit(gravity.source())
+                .len(),
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn forks_follow_the_variant_rules() -> Result<()> {
+        let b = "[[X, O, _], [_, O, _], [_, _, X]]";
+        let fork = |variant: &str| -> Result<i64> {
+            let session = <Session>::variant(variant.into())?;
+            Ok(session.fork_exists(session.decode(b.into())?))
+        };
+
+        assert_eq!(fork("3-3-3")?, 1);
+        assert_eq!(fork("3-3-3-exact")?, 1);
+        assert_eq!(fork("3-3-3-misere")?, -1);
+        Ok(())
     }
 }
@@ -11,7 +11,7 @@ use regex::Regex;
 use crate::game::State;
 use crate::game::error::GameError;
 use crate::game::mnk::Board;
-use crate::game::mnk::MAX_BOARD_SIDE;
+use crate::game::mnk::MAX_CELLS;
 use crate::game::mnk::NAME;
 use crate::game::mnk::Session;
 use crate::game::mnk::Symbol;
@@ -19,13 +19,16 @@ use crate::game::mnk::Symbol;
 /* MNK STATE ENCODING */
 
 pub const STATE_DEFAULT: &str = "[[_, _, _], [_, _, _], [_, _, _]]";
-pub const STATE_PATTERN: &str = r"^\[\s*(?:\[\s*(?:[XO_])(?:\s*,\s*[XO_])*\s*\])(?:\s*,\s*\[\s*(?:[XO_])(?:\s*,\s*[XO_])*\s*\])*\s*\]$";
+pub const STATE_PATTERN: &str = r"^\[[\[\]XOA-N_,\s]*\]$";
 pub const STATE_PROTOCOL: &str = "List of shape (m, n); that is, a list of \
 length m, where each item is a list of length n. Here, m and n are variant \
 parameters. Lists are comma-separated and bracket-enclosed, with whitespace \
 after each comma. The elements of the inner list should be one of the \
-following characters: 'X', 'O', or '_'. In all variants, the player with \
-symbol 'X' goes first.";
+following characters: 'X', 'O', or '_'. Boards with more dimensions are nested \
+lists of the shape given by the variant, so that a 2x2x2 board is a list of two \
+2x2 boards, such as '[[[X, _], [_, _]], [[_, _], [_, O]]]'. In all variants, \
+the player with symbol 'X' goes first, followed by 'O' and then (in variants \
+with more players) 'A', 'B', 'C', and so on up to 'N'.";
 
 /* API */
 
@@ -33,49 +36,72 @@ symbol 'X' goes first.";
 /// pre-verified game variant combined with the state update provided by the
 /// state encoded in `from`. This does not verify that the provided `from` is
 /// reachable in `session`'s game variant.
-pub fn decode_state_string(session: &Session, from: String) -> Result<State> {
+pub fn decode_state_string<const B: usize>(
+    session: &Session<B>,
+    from: String,
+) -> Result<State<B>> {
     check_state_pattern(&from)?;
     let symbols: Vec<Symbol> = from
         .chars()
-        .filter_map(|c| match c {
-            'X' => Some(Symbol::X),
-            'O' => Some(Symbol::O),
-            '_' => Some(Symbol::B),
-            _ => None,
-        })
+        .filter_map(Symbol::parse)
         .collect();
 
-    let m = session.m;
-    let n = session.n;
-    if symbols.len() != m * n {
+    if let Some(sym) = symbols
+        .iter()
+        .find(|s| s.0 as usize > session.players)
+    {
         bail!(
-            "Expected {} symbols for a {}×{} board, but found {}.",
-            m * n,
-            m,
-            n,
+            "Symbol '{}' does not belong to any of the {} players.",
+            sym.char(),
+            session.players
+        );
+    }
+
+    let shape = session
+        .sides
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join("×");
+
+    if symbols.len() != session.size() {
+        bail!(
+            "Expected {} symbols for a {} board, but found {}.",
+            session.size(),
+            shape,
             symbols.len()
         );
     }
 
-    let mut board: Board = [[Symbol::B; MAX_BOARD_SIDE]; MAX_BOARD_SIDE];
-    for (idx, &sym) in symbols.iter().enumerate() {
-        let row = idx / m;
-        let col = idx % n;
-        board[row][col] = sym;
+    let mut board: Board = [Symbol::B; MAX_CELLS];
+    board[..symbols.len()].copy_from_slice(&symbols);
+
+    // The symbols could still be nested in lists of the wrong shape, which is
+    // caught by comparing the string with the encoding of the board.
+    let strip = |s: &str| -> String {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    };
+
+    if strip(&from) != strip(&encode_state_string(session, &board)?) {
+        bail!("Expected nested lists of shape ({shape}), but found '{from}'.");
     }
 
-    let xs = symbols
-        .iter()
-        .filter(|&s| *s == Symbol::X)
-        .count();
+    // It is the turn of the first player in order of play (starting from X)
+    // among those who have placed the fewest symbols.
+    let count = |turn| {
+        symbols
+            .iter()
+            .filter(|&&s| s == session.symbol(turn))
+            .count()
+    };
 
-    let os = symbols
-        .iter()
-        .filter(|&s| *s == Symbol::O)
-        .count();
+    let turn = (1..=session.players)
+        .map(|i| i % session.players)
+        .min_by_key(|&turn| count(turn))
+        .unwrap();
 
-    // X's are turn 1, O's are turn 0.
-    let turn = if xs <= os { 1 } else { 0 };
     let state = Session::encode_state(session, turn, &board);
     Ok(state)
 }
@@ -83,30 +109,29 @@ pub fn decode_state_string(session: &Session, from: String) -> Result<State> {
 /// Returns an m,n,k game state string encoding using the parameters specified
 /// by a pre-verified game variant, corresponding to the input `board`. The turn
 /// is inferred from the board.
-pub fn encode_state_string(session: &Session, board: &Board) -> Result<String> {
-    let m = session.m;
-    let n = session.n;
-
-    let mut row_strs = Vec::with_capacity(m);
-    (0..m).for_each(|i| {
-        let mut elems = Vec::with_capacity(n);
-        for j in 0..n {
-            let ch = match board[i][j] {
-                Symbol::X => 'X',
-                Symbol::O => 'O',
-                Symbol::B => '_',
-            };
-            elems.push(ch.to_string());
-        }
-        row_strs.push(format!("[{}]", elems.join(", ")));
-    });
-
-    let full = format!("[{}]", row_strs.join(", "));
-    Ok(full)
+pub fn encode_state_string<const B: usize>(
+    session: &Session<B>,
+    board: &Board,
+) -> Result<String> {
+    Ok(nest(&session.sides, &board[..session.size()]))
 }
 
 /* HELPERS */
 
+/// Returns the encoding of `cells` as nested lists of shape `sides`.
+fn nest(sides: &[usize], cells: &[Symbol]) -> String {
+    if let Some((&side, rest)) = sides.split_first() {
+        let items: Vec<String> = cells
+            .chunks(cells.len() / side)
+            .map(|chunk| nest(rest, chunk))
+            .collect();
+
+        format!("[{}]", items.join(", "))
+    } else {
+        cells[0].char().to_string()
+    }
+}
+
 fn check_state_pattern(from: &String) -> Result<(), GameError> {
     let re = Regex::new(STATE_PATTERN).unwrap();
     if !re.is_match(from) {
@@ -145,8 +170,8 @@ mod test {
 
     #[test]
     fn no_state_equals_default_state() {
-        let with_none = Session::default();
-        let with_default = Session::default();
+        let with_none = <Session>::default();
+        let with_default = <Session>::default();
 
         assert_eq!(
             with_none.start,
@@ -162,6 +187,8 @@ mod test {
         let s3 = "[[X, O, X], [_, _, _]]".to_owned();
         let s4 = "[[X, O, X], [_, _, _], [X, _, O, _]]".to_owned();
         let s5 = "[[X, _, X],\t [_, _, _],\t [X, O, _], [_, _, _]]".to_owned();
+        let s6 = "[[X, _, X, _, _], [_, _, _, O]]".to_owned();
+        let s7 = "[[[X, _, X], [_, _, _], [_, _, O]]]".to_owned();
 
         fn f() -> Session {
             Session::default()
@@ -172,6 +199,8 @@ mod test {
         assert!(decode_state_string(&f(), s3).is_err());
         assert!(decode_state_string(&f(), s4).is_err());
         assert!(decode_state_string(&f(), s5).is_err());
+        assert!(decode_state_string(&f(), s6).is_err());
+        assert!(decode_state_string(&f(), s7).is_err());
     }
 
     #[test]
@@ -213,6 +242,17 @@ mod test {
         assert!(decode_state_string(&variant(v3)?, s1.clone()).is_err());
         assert!(decode_state_string(&variant(v3)?, s2.clone()).is_err());
         assert!(decode_state_string(&variant(v3)?, s3.clone()).is_ok());
+
+        let v4 = "2-2-2-2";
+        let s4 = "[[[X, _], [_, _]], [[_, _], [_, O]]]".to_owned();
+        assert!(decode_state_string(&variant(v4)?, s4.clone()).is_ok());
+        assert!(decode_state_string(&variant(v4)?, s3.clone()).is_err());
+        assert!(decode_state_string(&variant(v3)?, s4.clone()).is_err());
+
+        let v5 = "2-2-2-p3";
+        let s5 = "[[X, A], [O, _]]".to_owned();
+        assert!(decode_state_string(&variant(v5)?, s5.clone()).is_ok());
+        assert!(decode_state_string(&variant(v3)?, s5.clone()).is_err());
         Ok(())
     }
 
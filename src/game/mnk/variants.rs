@@ -9,64 +9,104 @@ use bitvec::field::BitField;
 use bitvec::order::Msb0;
 use regex::Regex;
 
+use crate::game::MAX_PLAYERS;
+use crate::game::PlayerCount;
 use crate::game::error::GameError;
-use crate::game::mnk::MAX_BOARD_SIDE;
+use crate::game::mnk::MAX_CELLS;
+use crate::game::mnk::MAX_DIMENSIONS;
 use crate::game::mnk::NAME;
+use crate::game::mnk::Rules;
 use crate::game::mnk::Session;
+use crate::game::mnk::board;
+use crate::game::mnk::cell_bits;
+use crate::game::mnk::turn_bits;
+use crate::game::util;
+use crate::solver::db;
 use crate::solver::db::SchemaBuilder;
 
 /* MNK VARIANT ENCODING */
 
 pub const VARIANT_DEFAULT: &str = "3-3-3";
-pub const VARIANT_PATTERN: &str =
-    r"^([1-9][0-9]*)-([1-9][0-9]*)-([1-9][0-9]*)$";
-
-pub const VARIANT_PROTOCOL: &str = "Three nonzero positive integers separated \
-by dashes, in the form M-N-K. Here, M and N are the dimensions of the board, \
-and K is the number of symbols which, when placed in a row, result in a win.";
+pub const VARIANT_PATTERN: &str = r"^([1-9][0-9]*)(-[1-9][0-9]*){2,}(-p[1-9][0-9]*)?(-misere)?(-exact)?(-gravity)?(-torus)?$";
+
+pub const VARIANT_PROTOCOL: &str = "Three or more nonzero positive integers \
+separated by dashes, in the form M-N-K. Here, M and N are the dimensions of \
+the board, and K is the number of symbols which, when placed in a row, result \
+in a win. Boards with more dimensions are given by listing the length of each \
+one before K, up to 6 dimensions; for example, '4-4-4-4' is Qubic, where lines \
+can extend along any direction through the cube. These can be followed by 'pP' \
+for a game between P players (from 2, the default, to 16), where each player \
+has their own symbol and completing K in a row makes every other player lose; \
+for example, '4-4-3-p3'. This can then be followed by any of the dash-separated \
+rule changes 'misere' (completing K in a row loses), 'exact' (only lines of \
+exactly K symbols are complete), 'gravity' (symbols fall to the lowest empty \
+cell of their column, along the first dimension), and 'torus' (lines wrap \
+around the edges of the board), in that order. For example, \
+'3-3-3-misere-torus'. On a torus, K cannot exceed the length of any dimension. \
+Boards can have up to 127 cells with 2 players, and fewer with more players.";
 
 /* API */
 
 /// Returns an m,n,k-game session set up using the parameters specified by
 /// `variant`.
-pub fn parse_variant(variant: String) -> Result<Session> {
+pub fn parse_variant<const B: usize>(variant: String) -> Result<Session<B>> {
     check_variant_pattern(&variant)?;
     let params = parse_parameters(&variant)?;
     check_param_count(&params)?;
     check_params_are_positive(&params)?;
-    check_dimensionality(&params)?;
+    let (&k, sides) = params.split_last().unwrap();
+    let players = parse_players(&variant)?;
+    check_player_count(players)?;
+    check_dimensionality::<B>(sides, players)?;
+    let rules = parse_rules(&variant);
+    check_rules(sides, k, &rules)?;
 
     let table = format!("{}_{}", NAME, variant);
     let schema = SchemaBuilder::new(&table)
-        .players(2)
-        .key("state", "INTEGER")
+        .players(players)
+        .prefix(util::prefix()?)
+        .key("state", db::key_type::<B>())
         .column("remoteness", "INTEGER")
         .column("player", "INTEGER")
-        .column("orbit_rep", "INTEGER")
-        .column("fork_exists", "INTEGER")
-        .column("ply", "INTEGER")
-        .column("center_control", "INTEGER")
-        .column("corner_count", "INTEGER")
-        .column("edge_count", "INTEGER")
         .build()?;
 
-    let mut state = BitArray::<_, Msb0>::ZERO;
-    state[..1].store_be(1);
+    let mut state = BitArray::<[u8; B], Msb0>::ZERO;
+    state[..turn_bits(players)].store_be(1);
 
     Ok(Session {
+        variant,
         schema,
         start: state.data,
-        m: params[0],
-        n: params[1],
-        k: params[2],
+        players,
+        lines: board::lines(sides, k, &rules),
+        symmetries: board::symmetries(sides, &rules),
+        sides: sides.to_vec(),
+        rules,
     })
 }
 
+/// Returns the number of bits needed to encode the states of `variant`, or
+/// `None` if its dimensions or player count cannot be parsed.
+pub fn encoding_bits(variant: &str) -> Option<usize> {
+    let params = parse_parameters(variant).ok()?;
+    let players = parse_players(variant)
+        .ok()
+        .filter(|&p| p >= 2)?;
+
+    let (_, sides) = params.split_last()?;
+    sides
+        .iter()
+        .try_fold(1usize, |acc, &s| acc.checked_mul(s))?
+        .checked_mul(cell_bits(players))?
+        .checked_add(turn_bits(players))
+}
+
 /* HELPERS */
 
 fn parse_parameters(variant: &str) -> Result<Vec<usize>, GameError> {
     let params: Result<Vec<usize>, _> = variant
         .split('-')
+        .take_while(|s| s.starts_with(|c: char| c.is_ascii_digit()))
         .map(|int_string| {
             int_string
                 .parse::<usize>()
@@ -79,6 +119,37 @@ fn parse_parameters(variant: &str) -> Result<Vec<usize>, GameError> {
     params
 }
 
+fn parse_players(variant: &str) -> Result<PlayerCount, GameError> {
+    let count = variant
+        .split('-')
+        .find_map(|s| s.strip_prefix('p'));
+
+    if let Some(count) = count {
+        count
+            .parse::<PlayerCount>()
+            .map_err(|e| GameError::VariantMalformed {
+                game: NAME,
+                hint: e.to_string(),
+            })
+    } else {
+        Ok(2)
+    }
+}
+
+fn parse_rules(variant: &str) -> Rules {
+    let rules: Vec<&str> = variant
+        .split('-')
+        .skip_while(|s| s.starts_with(|c: char| c.is_ascii_digit()))
+        .collect();
+
+    Rules {
+        misere: rules.contains(&"misere"),
+        exact: rules.contains(&"exact"),
+        gravity: rules.contains(&"gravity"),
+        torus: rules.contains(&"torus"),
+    }
+}
+
 fn check_variant_pattern(variant: &str) -> Result<(), GameError> {
     let re = Regex::new(VARIANT_PATTERN).unwrap();
     if !re.is_match(variant) {
@@ -94,11 +165,14 @@ fn check_variant_pattern(variant: &str) -> Result<(), GameError> {
 }
 
 fn check_param_count(params: &[usize]) -> Result<(), GameError> {
-    if params.len() != 3 {
+    if params.len() < 3 || params.len() > MAX_DIMENSIONS + 1 {
         Err(GameError::VariantMalformed {
             game: NAME,
-            hint: "String needs to have exactly 3 dash-separated integers."
-                .to_string(),
+            hint: format!(
+                "String needs to have between 3 and {} dash-separated \
+                integers.",
+                MAX_DIMENSIONS + 1,
+            ),
         })
     } else {
         Ok(())
@@ -106,7 +180,7 @@ fn check_param_count(params: &[usize]) -> Result<(), GameError> {
 }
 
 fn check_params_are_positive(params: &[usize]) -> Result<(), GameError> {
-    if params.iter().any(|&x| x == 0) {
+    if params.contains(&0) {
         Err(GameError::VariantMalformed {
             game: NAME,
             hint: "All integers in the string must be positive.".to_string(),
@@ -116,37 +190,74 @@ fn check_params_are_positive(params: &[usize]) -> Result<(), GameError> {
     }
 }
 
-fn check_dimensionality(params: &[usize]) -> Result<(), GameError> {
-    if params[0] > MAX_BOARD_SIDE {
+fn check_player_count(players: PlayerCount) -> Result<(), GameError> {
+    if !(2..=MAX_PLAYERS).contains(&players) {
+        Err(GameError::VariantMalformed {
+            game: NAME,
+            hint: format!(
+                "The number of players must be between 2 and {MAX_PLAYERS}, \
+                but it is {players}.",
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_dimensionality<const B: usize>(
+    sides: &[usize],
+    players: PlayerCount,
+) -> Result<(), GameError> {
+    if let Some(side) = sides
+        .iter()
+        .find(|&&s| s > MAX_CELLS)
+    {
         return Err(GameError::VariantMalformed {
             game: NAME,
             hint: format!(
-                "Dimension 'm = {}' is too large. Maximum is {}.",
-                params[0], MAX_BOARD_SIDE,
+                "Dimension of length {side} is too large. Maximum is \
+                {MAX_CELLS}.",
             ),
         });
     }
 
-    if params[1] > MAX_BOARD_SIDE {
+    // The first bits of each state encoding are used to store turns, and the
+    // rest are used to store board cells. Each cell needs enough bits to hold
+    // the symbol of any player or a blank, which is 2 bits for 2 players.
+    let cells: usize = sides.iter().product();
+    let (t, w) = (turn_bits(players), cell_bits(players));
+    if w * cells + t > 8 * B {
         return Err(GameError::VariantMalformed {
             game: NAME,
             hint: format!(
-                "Dimension 'n = {}' is too large. Maximum is {}.",
-                params[1], MAX_BOARD_SIDE,
+                "Dimensions are too large for state encoding scheme. Ensure \
+                that (cells * {w} + {t}) <= {}. Currently, there are {} \
+                cells.",
+                8 * B,
+                cells,
             ),
         });
     }
 
-    // State encodings are 64 bits. One bit is used to store turns efficiently.
-    // The remaining 63 bits are used to store board slots. Each slot needs 2
-    // bits. So, 2 * m * n cannot be  greater than 63.
-    if 2 * params[0] * params[1] > 63 {
+    Ok(())
+}
+
+fn check_rules(
+    sides: &[usize],
+    k: usize,
+    rules: &Rules,
+) -> Result<(), GameError> {
+    // A line longer than a side of a torus would cover some of its cells more
+    // than once, which would make it possible to complete it early.
+    let shortest = sides.iter().min().copied();
+    if rules.torus && shortest.is_some_and(|s| k > s) {
         return Err(GameError::VariantMalformed {
             game: NAME,
             hint: format!(
-                "Dimensions are too large for state encoding scheme. Ensure \
-                that (m * n * 2) < 63. Currently, it is {}.",
-                params[0] * params[1],
+                "On a torus, 'k = {}' cannot exceed the length of any \
+                dimension, the shortest of which is {}.",
+                k,
+                shortest.unwrap_or_default(),
             ),
         });
     }
@@ -160,6 +271,7 @@ fn check_dimensionality(params: &[usize]) -> Result<(), GameError> {
 mod test {
 
     use super::*;
+    use crate::game::mnk::WIDE_STATE_BYTES;
     use crate::game::*;
 
     #[test]
@@ -175,19 +287,19 @@ mod test {
 
     #[test]
     fn initialization_success_with_no_variant() {
-        let _ = Session::default();
-        let with_default = Session::variant(VARIANT_DEFAULT.to_owned());
+        let _ = <Session>::default();
+        let with_default = <Session>::variant(VARIANT_DEFAULT.to_owned());
         assert!(with_default.is_ok());
     }
 
     #[test]
     fn no_variant_equals_default_variant() -> Result<()> {
-        let with_none = Session::default();
-        let with_default = Session::variant(VARIANT_DEFAULT.to_owned())?;
+        let with_none = <Session>::default();
+        let with_default = <Session>::variant(VARIANT_DEFAULT.to_owned())?;
         assert_eq!(with_none.start, with_default.start);
-        assert_eq!(with_none.m, with_default.m);
-        assert_eq!(with_none.n, with_default.n);
-        assert_eq!(with_none.k, with_default.k);
+        assert_eq!(with_none.sides, with_default.sides);
+        assert_eq!(with_none.lines, with_default.lines);
+        assert_eq!(with_none.rules, Rules::default());
         Ok(())
     }
 
@@ -199,6 +311,16 @@ mod test {
         let v4 = "34--236-3";
         let v5 = "364";
         let v6 = "-234-256";
+        let v7 = "3-3-3-torus-misere";
+        let v8 = "3-3-3-misere-misere";
+        let v9 = "3-3-3-notakto";
+        let v10 = "3-2-3-torus";
+        let v11 = "2-2-2-2-2-2-2-2";
+        let v12 = "3-3-0-3";
+        let v13 = "3-3-3-p1";
+        let v14 = "3-3-3-p17";
+        let v15 = "3-3-3-misere-p3";
+        let v16 = "5-5-3-p4";
 
         fn wrapper(v: &'static str) -> Result<Session> {
             parse_variant(v.to_owned())
@@ -210,6 +332,16 @@ mod test {
         assert!(wrapper(v4).is_err());
         assert!(wrapper(v5).is_err());
         assert!(wrapper(v6).is_err());
+        assert!(wrapper(v7).is_err());
+        assert!(wrapper(v8).is_err());
+        assert!(wrapper(v9).is_err());
+        assert!(wrapper(v10).is_err());
+        assert!(wrapper(v11).is_err());
+        assert!(wrapper(v12).is_err());
+        assert!(wrapper(v13).is_err());
+        assert!(wrapper(v14).is_err());
+        assert!(wrapper(v15).is_err());
+        assert!(wrapper(v16).is_err());
     }
 
     #[test]
@@ -218,6 +350,10 @@ mod test {
         let v2 = "3-3-3";
         let v3 = "2-4-2";
         let v4 = "3-2-3";
+        let v5 = "2-2-2-2";
+        let v6 = "3-3-3-3-torus";
+        let v7 = "3-3-3-p3-misere";
+        let v8 = "2-2-2-p16";
 
         fn wrapper(v: &'static str) -> Result<Session> {
             parse_variant(v.to_owned())
@@ -227,6 +363,31 @@ mod test {
         assert!(wrapper(v2).is_ok());
         assert!(wrapper(v3).is_ok());
         assert!(wrapper(v4).is_ok());
+        assert!(wrapper(v5).is_ok());
+        assert!(wrapper(v6).is_ok());
+        assert!(wrapper(v7).is_ok());
+        assert!(wrapper(v8).is_ok());
+    }
+
+    #[test]
+    fn rule_changes_are_parsed() -> Result<()> {
+        let session: Session = parse_variant("5-6-4-gravity".to_owned())?;
+        assert_eq!(session.sides, [5, 6]);
+        assert_eq!(
+            session.rules,
+            Rules {
+                gravity: true,
+                ..Rules::default()
+            }
+        );
+
+        let session: Session =
+            parse_variant("3-3-3-misere-exact-gravity-torus".into())?;
+        assert!(session.rules.misere);
+        assert!(session.rules.exact);
+        assert!(session.rules.gravity);
+        assert!(session.rules.torus);
+        Ok(())
     }
 
     #[test]
@@ -245,4 +406,23 @@ mod test {
         assert!(wrapper(v3).is_err());
         assert!(wrapper(v4).is_err());
     }
+
+    #[test]
+    fn wide_encodings_fit_larger_boards() {
+        let v1 = "4-4-4-4";
+        let v2 = "3-3-3-3-3-3";
+        let v3 = "8-16-5";
+        let v4 = "5-5-5-3";
+
+        fn wrapper<const B: usize>(v: &'static str) -> Result<Session<B>> {
+            parse_variant(v.to_owned())
+        }
+
+        assert!(wrapper::<8>(v1).is_err());
+        assert!(wrapper::<WIDE_STATE_BYTES>(v1).is_ok());
+        assert!(wrapper::<WIDE_STATE_BYTES>(v2).is_err());
+        assert!(wrapper::<WIDE_STATE_BYTES>(v3).is_err());
+        assert!(wrapper::<WIDE_STATE_BYTES>(v4).is_ok());
+        assert_eq!(encoding_bits(v1), Some(129));
+    }
 }
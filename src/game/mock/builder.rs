@@ -8,6 +8,7 @@ use anyhow::Result;
 use anyhow::bail;
 use petgraph::Direction;
 use petgraph::Graph;
+use petgraph::algo::is_cyclic_directed;
 use petgraph::graph::NodeIndex;
 
 use std::collections::HashMap;
@@ -16,6 +17,7 @@ use std::collections::HashSet;
 use crate::game::PlayerCount;
 use crate::game::mock::Node;
 use crate::game::mock::Session;
+use crate::game::util;
 use crate::solver::db::Schema;
 use crate::solver::db::SchemaBuilder;
 
@@ -31,16 +33,20 @@ type Finalized = bool;
 ///
 /// # Example
 ///
-/// ```no_run
+/// ```
+/// # use gamesman_nova::node;
+/// # use gamesman_nova::game::{Codec, Implicit};
+/// # use gamesman_nova::game::mock::{Node, SessionBuilder};
+/// # fn main() -> anyhow::Result<()> {
 /// // Long-form node initialization
 /// let s0 = Node::Medial(0);
 /// let s1 = Node::Medial(1);
-/// let s2 = Node::Terminal(vec![1, -1]);
+/// let s2 = Node::Terminal(0, vec![1, -1]);
 ///
 /// // Macro node initialization (equivalent)
 /// let s0 = node!(0);
 /// let s1 = node!(1);
-/// let s2 = node!([1, -1]);
+/// let s2 = node![0; 1, -1];
 ///
 /// let session = SessionBuilder::new("example")
 ///     .edge(&s0, &s1)?
@@ -49,14 +55,17 @@ type Finalized = bool;
 ///     .source(&s0)?
 ///     .build()?;
 ///
-/// assert_eq!(session.players, 2);
+/// assert_eq!(session.adjacent(session.source()).len(), 2);
+/// # Ok(())
+/// # }
 /// ```
 pub struct SessionBuilder<'a> {
     inserted: HashMap<*const Node, NodeIndex>,
+    labels: HashMap<NodeIndex, String>,
     players: (PlayerCount, Finalized),
     source: Option<NodeIndex>,
     game: Graph<&'a Node, ()>,
-    name: &'static str,
+    name: String,
 }
 
 /* BUILDER IMPLEMENTATION */
@@ -65,13 +74,14 @@ impl<'a> SessionBuilder<'a> {
     /// Initialize a builder struct for a graph game with an empty graph, no
     /// source state, and a given `name` that will be eventually used for
     /// the constructed game session's `id`.
-    pub fn new(name: &'static str) -> Self {
+    pub fn new(name: &str) -> Self {
         SessionBuilder {
             inserted: HashMap::new(),
+            labels: HashMap::new(),
             players: (0, false),
             source: None,
             game: Graph::new(),
-            name,
+            name: name.to_owned(),
         }
     }
 
@@ -126,23 +136,60 @@ impl<'a> SessionBuilder<'a> {
         }
     }
 
-    /// Instantiate a `Session` encoding the constructed game graph. Fails if no
-    /// source state was specified, there exist non-terminal nodes with no
-    /// outgoing edges, or no terminal nodes are reachable from the source
-    /// state (assuming it is valid).
-    pub fn build(self) -> Result<Session<'a>> {
+    /// Indicate that the state of `node` is encoded as `label` in the game
+    /// being built, instead of as the order in which it was added. The
+    /// indicated `node` must have already been added to the game. Fails if
+    /// there is no such existing node.
+    pub fn label(mut self, node: &Node, label: &str) -> Result<Self> {
+        if let Some(&index) = self
+            .inserted
+            .get(&(node as *const Node))
+        {
+            self.labels
+                .insert(index, label.to_owned());
+            Ok(self)
+        } else {
+            bail! {
+                    "There was an attempt to label a node of mock game '{}', \
+                    but the indicated node has not been added to the game yet.",
+                    self.name,
+            }
+        }
+    }
+
+    /// Instantiate a `Session` encoding the constructed game graph, which owns
+    /// copies of the nodes that were added to it. Fails if no source state was
+    /// specified, there exist non-terminal nodes with no outgoing edges, no
+    /// terminal nodes are reachable from the source state (assuming it is
+    /// valid), or the game graph contains a cycle.
+    pub fn build(self) -> Result<Session> {
         let source = self.check_source_state()?;
         self.check_terminal_state(source)?;
         self.check_outgoing_edges()?;
+        self.check_acyclic()?;
 
         let (players, _) = self.players;
-        let schema = self.schema(players, self.name)?;
+        let schema = self.schema(players, &self.name)?;
+        let labels = self
+            .game
+            .node_indices()
+            .map(|i| {
+                self.labels
+                    .get(&i)
+                    .cloned()
+                    .unwrap_or_else(|| i.index().to_string())
+            })
+            .collect();
+
         Ok(Session {
-            inserted: self.inserted,
+            labels,
             players,
             schema,
             source,
-            game: self.game,
+            game: self
+                .game
+                .map(|_, &node| node.clone(), |_, _| ()),
+            variant: self.name.clone(),
             name: self.name,
         })
     }
@@ -277,8 +324,21 @@ impl<'a> SessionBuilder<'a> {
         }
     }
 
+    fn check_acyclic(&self) -> Result<()> {
+        if is_cyclic_directed(&self.game) {
+            bail! {
+                    "The constructed game '{}' contains a cycle, but only \
+                    acyclic games are supported.",
+                    self.name
+            }
+        } else {
+            Ok(())
+        }
+    }
+
     fn schema(&self, players: PlayerCount, table: &str) -> Result<Schema> {
         SchemaBuilder::new(table)
+            .prefix(util::prefix()?)
             .players(players)
             .key("state", "INTEGER")
             .column("remoteness", "INTEGER")
@@ -502,7 +562,7 @@ mod tests {
     }
 
     #[test]
-    fn build_simple_cyclic_game() -> Result<()> {
+    fn cannot_build_cyclic_game() -> Result<()> {
         let a = node!(0);
         let b = node!(1);
         let c = node!(0);
@@ -524,11 +584,9 @@ mod tests {
             .edge(&e, &f)?
             .edge(&f, &t2)?
             .source(&a)?
-            .build()?;
-
-        game.visualize(MODULE_NAME)?;
-        assert_eq!(game.players, 4);
+            .build();
 
+        assert!(game.is_err());
         Ok(())
     }
 }
@@ -1,9 +1,10 @@
-//! Mock Test Game Module
+//! # Graph Game Module
 //!
 //! This module provides a way to represent extensive-form games by declaring
 //! the game via a graph and assigning special conditions to nodes. This makes
-//! creating example games a matter of simply declaring them and wrapping them
-//! in any necessary external interface implementations.
+//! creating example games a matter of simply declaring them, either through a
+//! `SessionBuilder` from Rust or through a game file (as described by the
+//! game's variant protocol), and solving them like any other game offering.
 
 use anyhow::Context;
 use anyhow::Result;
@@ -20,12 +21,21 @@ use rusqlite::Statement;
 use rusqlite::Transaction;
 use rusqlite::params_from_iter;
 
-use std::collections::HashMap;
-
+use crate::game::Codec;
+use crate::game::Feature;
+use crate::game::Features;
+use crate::game::Forward;
+use crate::game::GameData;
 use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::Parameters;
 use crate::game::Player;
 use crate::game::PlayerCount;
 use crate::game::State;
+use crate::game::Variable;
+use crate::game::Variant;
+use crate::game::mock::states::*;
+use crate::game::mock::variants::*;
 use crate::interface::IOMode;
 use crate::solver::Game;
 use crate::solver::IUtility;
@@ -33,106 +43,198 @@ use crate::solver::IntegerUtility;
 use crate::solver::Persistent;
 use crate::solver::Queries;
 use crate::solver::Solution;
+use crate::solver::Tabular;
 use crate::solver::db::Schema;
 
 /* RE-EXPORTS */
 
 pub use builder::SessionBuilder;
+#[cfg(test)]
+pub use random::Generator;
 
 /* SUBMODULES */
 
 mod builder;
+mod states;
+mod variants;
+
+#[cfg(test)]
+mod random;
+
+/* GAME DATA */
+
+const NAME: &str = "graph";
+const AUTHORS: &str = "Max Fierro <maxfierro@berkeley.edu>";
+const ABOUT: &str = "An arbitrary extensive-form game, declared as a directed \
+graph in a game file. Each node of the graph is either medial, in which case \
+it indicates whose turn it is to move, or terminal, in which case it also \
+holds the utility that each player obtains when the game ends there. Moves are \
+the edges of the graph, and the game begins at a designated source node. This \
+makes it possible to solve and analyze games that have no implementation of \
+their own.";
 
 /* DEFINITIONS */
 
 /// Represents an initialized session of an abstract graph game. This can be
-/// constructed using `SessionBuilder`.
-pub struct Session<'a> {
-    inserted: HashMap<*const Node, NodeIndex>,
+/// constructed using `SessionBuilder`, or from a game file.
+pub struct Session {
+    labels: Vec<String>,
     players: PlayerCount,
     source: NodeIndex<DefaultIx>,
+    variant: Variant,
     schema: Schema,
-    game: Graph<&'a Node, ()>,
-    name: &'static str,
+    game: Graph<Node, ()>,
+    name: String,
 }
 
 /// Indicates whether a game state node is terminal (there are no outgoing moves
 /// or edges) or medial (it is possible to transition out of it). Nodes in the
 /// terminal stage have an associated utility vector, and medial nodes have a
 /// turn encoding whose player's action is pending.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Node {
+    /// A node with no outgoing edges, with the player in turn and the utility
+    /// of each player at it.
     Terminal(Player, Vec<IUtility>),
+
+    /// A node with outgoing edges, with the player in turn at it.
     Medial(Player),
 }
 
+/// The nodes and edges of a game graph along with a label for each node, which
+/// own the data that a `SessionBuilder` borrows. Edges and the source node are
+/// given as indices into the nodes.
+pub struct Blueprint {
+    nodes: Vec<Node>,
+    labels: Vec<String>,
+    edges: Vec<(usize, usize)>,
+    source: usize,
+}
+
 /* API IMPLEMENTATION */
 
-impl<'a> Session<'a> {
+impl Session {
     /// Return a name or identifier corresponding to this game.
-    pub fn name(&self) -> &'a str {
-        self.name
+    #[cfg(test)]
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Return the number of players in this game.
+    #[cfg(test)]
     pub fn players(&self) -> PlayerCount {
         self.players
     }
 
-    /// Return the state hash being internally used for `node`.
-    pub fn state(&self, node: &Node) -> Option<State> {
-        self.inserted
-            .get(&(node as *const Node))
-            .map(|idx| {
-                let mut state = BitArray::<_, Msb0>::ZERO;
-                state.store_be::<DefaultIx>(idx.index() as DefaultIx);
-                state.data
-            })
-    }
-
     /// Return an immutable borrow of the graph underlying the game.
-    pub fn graph(&self) -> &Graph<&Node, ()> {
+    #[cfg(test)]
+    pub fn graph(&self) -> &Graph<Node, ()> {
         &self.game
     }
+
+    /// Return the index of the node corresponding to `state` in the graph
+    /// underlying the game.
+    pub fn index(state: State) -> NodeIndex {
+        NodeIndex::from(BitArray::<_, Msb0>::from(state).load_be::<DefaultIx>())
+    }
+}
+
+impl Blueprint {
+    /// Instantiate a `Session` named `name` over the nodes and edges of this
+    /// blueprint, where states are encoded by the labels of their nodes.
+    pub fn session(&self, name: &str) -> Result<Session> {
+        let mut builder = SessionBuilder::new(name);
+        for &(from, to) in &self.edges {
+            let (a, b) = (&self.nodes[from], &self.nodes[to]);
+            builder = builder
+                .edge(a, b)?
+                .label(a, &self.labels[from])?
+                .label(b, &self.labels[to])?;
+        }
+
+        builder
+            .source(&self.nodes[self.source])?
+            .build()
+    }
 }
 
 /* PRIVATE IMPLEMENTATION */
 
-impl Session<'_> {
+impl Session {
     fn adjacent(&self, state: State, dir: Direction) -> Vec<State> {
         self.game
-            .neighbors_directed(
-                NodeIndex::from(
-                    BitArray::<_, Msb0>::from(state).load_be::<DefaultIx>(),
-                ),
-                dir,
-            )
-            .map(|n| {
-                let mut state: BitArray<_, Msb0> = BitArray::ZERO;
-                state.store_be(n.index());
-                state.data
-            })
+            .neighbors_directed(Self::index(state), dir)
+            .map(Self::encode_index)
             .collect()
     }
 
     fn node(&self, state: State) -> &Node {
-        self.game[NodeIndex::from(
-            BitArray::<_, Msb0>::from(state).load_be::<DefaultIx>(),
-        )]
+        &self.game[Self::index(state)]
+    }
+
+    fn encode_index(index: NodeIndex) -> State {
+        let mut state = BitArray::<_, Msb0>::ZERO;
+        state.store_be::<DefaultIx>(index.index() as DefaultIx);
+        state.data
+    }
+}
+
+/* IMPLEMENTATIONS */
+
+impl Default for Session {
+    fn default() -> Self {
+        parse_variant(VARIANT_DEFAULT.to_owned())
+            .expect("Failed to parse default variant.")
+    }
+}
+
+impl Information for Session {
+    fn info() -> GameData {
+        GameData {
+            name: NAME,
+            authors: AUTHORS,
+            about: ABOUT,
+
+            variant_protocol: VARIANT_PROTOCOL,
+            variant_pattern: VARIANT_PATTERN,
+            variant_default: VARIANT_DEFAULT,
+
+            state_default: STATE_DEFAULT,
+            state_pattern: STATE_PATTERN,
+            state_protocol: STATE_PROTOCOL,
+        }
+    }
+}
+
+impl Features for Session {
+    fn features() -> Vec<Feature<Self>> {
+        Vec::new()
+    }
+}
+
+impl Parameters for Session {
+    fn variant_string(&self) -> &str {
+        &self.variant
+    }
+
+    fn player_count(&self) -> PlayerCount {
+        self.players
     }
 }
 
-/* UTILITY IMPLEMENTATIONS */
+impl Variable for Session {
+    fn variant(variant: Variant) -> Result<Self> {
+        parse_variant(variant).context("Malformed game variant.")
+    }
+}
 
-impl Implicit for Session<'_> {
+impl Implicit for Session {
     fn adjacent(&self, state: State) -> Vec<State> {
         self.adjacent(state, Direction::Outgoing)
     }
 
     fn source(&self) -> State {
-        let mut state = BitArray::<_, Msb0>::ZERO;
-        state.store_be::<DefaultIx>(self.source.index() as DefaultIx);
-        state.data
+        Self::encode_index(self.source)
     }
 
     fn sink(&self, state: State) -> bool {
@@ -143,9 +245,25 @@ impl Implicit for Session<'_> {
     }
 }
 
+impl Codec for Session {
+    fn decode(&self, string: String) -> Result<State> {
+        decode_state_string(self, string)
+    }
+
+    fn encode(&self, state: State) -> Result<String> {
+        Ok(self.labels[Self::index(state).index()].clone())
+    }
+}
+
+impl Forward for Session {
+    fn set_verified_start(&mut self, state: State) {
+        self.source = Self::index(state);
+    }
+}
+
 /* SOLVING IMPLEMENTATIONS */
 
-impl<const N: PlayerCount> Game<N> for Session<'_> {
+impl<const N: PlayerCount> Game<N> for Session {
     fn turn(&self, state: State) -> Player {
         match self.node(state) {
             Node::Terminal(player, _) => *player,
@@ -154,7 +272,7 @@ impl<const N: PlayerCount> Game<N> for Session<'_> {
     }
 }
 
-impl<const N: PlayerCount> IntegerUtility<N> for Session<'_> {
+impl<const N: PlayerCount> IntegerUtility<N> for Session {
     fn utility(&self, state: State) -> [IUtility; N] {
         match self.node(state) {
             Node::Terminal(_, payoffs) => {
@@ -169,7 +287,7 @@ impl<const N: PlayerCount> IntegerUtility<N> for Session<'_> {
     }
 }
 
-impl<const N: PlayerCount> Persistent<N> for Session<'_> {
+impl<const N: PlayerCount> Persistent<N> for Session {
     fn prepare(
         &mut self,
         tx: &mut Transaction,
@@ -240,15 +358,28 @@ impl<const N: PlayerCount> Persistent<N> for Session<'_> {
     }
 }
 
+impl Tabular for Session {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::game::conformance;
+    use crate::game::conformance::Sum;
     use crate::node;
     use anyhow::Result;
 
     const MODULE_NAME: &str = "mock-core-tests";
 
+    #[test]
+    fn session_conforms_to_game_contracts() -> Result<()> {
+        conformance::suite::<2, 8, Session>(&[""], Sum::Zero)
+    }
+
     #[test]
     fn get_unique_node_states() -> Result<()> {
         let s1 = node!(0);
@@ -267,32 +398,25 @@ mod tests {
             .edge(&s4, &s5)?
             .edge(&s4, &t1)?
             .edge(&s5, &t2)?
+            .label(&s1, "s1")?
+            .label(&s2, "s2")?
+            .label(&s3, "s3")?
+            .label(&s4, "s4")?
+            .label(&s5, "s5")?
+            .label(&t1, "t1")?
+            .label(&t2, "t2")?
             .source(&s1)?
             .build()?;
 
         g.visualize(MODULE_NAME)?;
-        let states = [
-            g.state(&s1),
-            g.state(&s2),
-            g.state(&s3),
-            g.state(&s4),
-            g.state(&s5),
-            g.state(&t1),
-            g.state(&t2),
-        ];
-
-        let contains_none = states.iter().any(Option::is_none);
-        assert!(!contains_none);
-
-        let states: Vec<State> = states
-            .iter()
-            .map(|s| s.unwrap())
-            .collect();
+        let states = ["s1", "s2", "s3", "s4", "s5", "t1", "t2"]
+            .into_iter()
+            .map(|label| g.decode(label.into()))
+            .collect::<Result<Vec<State>>>()?;
 
         let repeats = states.iter().any(|&i| {
             states[(1 + BitArray::<_, Msb0>::from(i).load_be::<usize>())..]
-                .iter()
-                .any(|&j| i == j)
+                .contains(&i)
         });
 
         assert!(!repeats);
@@ -313,13 +437,16 @@ mod tests {
             .edge(&s2, &s3)?
             .edge(&s2, &t1)?
             .edge(&s3, &t2)?
+            .label(&s1, "s1")?
+            .label(&t1, "t1")?
+            .label(&t2, "t2")?
             .source(&s1)?
             .build()?;
 
         g.visualize(MODULE_NAME)?;
-        let source = g.state(&s1).unwrap();
-        let sink1 = g.state(&t1).unwrap();
-        let sink2 = g.state(&t2).unwrap();
+        let source = g.decode("s1".into())?;
+        let sink1 = g.decode("t1".into())?;
+        let sink2 = g.decode("t2".into())?;
 
         assert_eq!(g.source(), source);
         assert!(g.sink(sink1));
@@ -341,16 +468,21 @@ mod tests {
             .edge(&s1, &s3)?
             .edge(&s2, &t1)?
             .edge(&s3, &t2)?
+            .label(&s1, "s1")?
+            .label(&s2, "s2")?
+            .label(&s3, "s3")?
+            .label(&t1, "t1")?
+            .label(&t2, "t2")?
             .source(&s1)?
             .build()?;
 
         g.visualize(MODULE_NAME)?;
-        let s1_state = g.state(&s1).unwrap();
-        let s2_state = g.state(&s2).unwrap();
-        let s3_state = g.state(&s3).unwrap();
+        let s1_state = g.decode("s1".into())?;
+        let s2_state = g.decode("s2".into())?;
+        let s3_state = g.decode("s3".into())?;
 
-        let t1_state = g.state(&t1).unwrap();
-        let t2_state = g.state(&t2).unwrap();
+        let t1_state = g.decode("t1".into())?;
+        let t2_state = g.decode("t2".into())?;
 
         let s1_pro = g.adjacent(s1_state, Direction::Outgoing);
         let s2_pro = g.adjacent(s2_state, Direction::Outgoing);
@@ -0,0 +1,217 @@
+//! Mock Random Game Generator Module
+//!
+//! This module provides a way to generate random extensive-form games with a
+//! configurable shape, which can then be turned into a game `Session` through
+//! a `SessionBuilder`. This makes it possible to test solvers against
+//! a large number of games that no one had to draw by hand.
+
+use anyhow::Result;
+use anyhow::bail;
+use fastrand::Rng;
+
+use crate::game::PlayerCount;
+use crate::game::mock::Blueprint;
+use crate::game::mock::Node;
+use crate::solver::IUtility;
+
+/* CONSTANTS */
+
+/// Probability that a node in an intermediate layer is made terminal.
+const EARLY_TERMINAL_PROBABILITY: f64 = 0.2;
+
+/* DEFINITIONS */
+
+/// Builder pattern for the parameters of randomly generated games. Games are
+/// generated in layers, where the first layer only contains the source node,
+/// the last layer only contains terminal nodes, and every edge goes from a
+/// node to a node in a later layer (so that all generated games are acyclic).
+///
+/// # Example
+///
+/// ```no_run
+/// let mut rng = Rng::with_seed(0);
+/// let blueprint = Generator::new(3)
+///     .depth(5)
+///     .branching(3)
+///     .generate(&mut rng)?;
+///
+/// let session = blueprint.session("random")?;
+/// assert_eq!(session.players(), 3);
+/// ```
+pub struct Generator {
+    players: PlayerCount,
+    depth: usize,
+    branching: usize,
+    width: usize,
+    payoff: IUtility,
+}
+
+/* BUILDER IMPLEMENTATION */
+
+impl Generator {
+    /// Initialize a generator of games between `players` players with a depth
+    /// of 4 layers, at most 3 outgoing edges per medial node, at most 4 nodes
+    /// per layer, and utilities in the range `-5..=5`.
+    pub fn new(players: PlayerCount) -> Self {
+        Generator {
+            players,
+            depth: 4,
+            branching: 3,
+            width: 4,
+            payoff: 5,
+        }
+    }
+
+    /// Set the number of layers after the source node in generated games,
+    /// which is the length of their longest possible path.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Set the maximum number of outgoing edges of medial nodes.
+    pub fn branching(mut self, branching: usize) -> Self {
+        self.branching = branching;
+        self
+    }
+
+    /// Set the maximum number of nodes in each layer after the source.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the largest absolute value of the utilities at terminal nodes.
+    pub fn payoff(mut self, payoff: IUtility) -> Self {
+        self.payoff = payoff;
+        self
+    }
+
+    /// Generate the nodes and edges of a random game, drawing all random
+    /// choices from `rng`. Fails if the parameters of the generator do not
+    /// allow for any valid game.
+    pub fn generate(&self, rng: &mut Rng) -> Result<Blueprint> {
+        if self.players == 0 {
+            bail!("Cannot generate games with no players.")
+        } else if self.depth == 0 || self.branching == 0 || self.width == 0 {
+            bail!(
+                "Cannot generate games with a depth, branching, or width of \
+                zero."
+            )
+        }
+
+        let mut layers = vec![vec![0]];
+        let mut nodes = vec![self.medial(rng)];
+        for layer in 1..=self.depth {
+            let size = rng.usize(1..=self.width);
+            layers.push((nodes.len()..nodes.len() + size).collect());
+            for _ in 0..size {
+                let last = layer == self.depth;
+                if last || rng.f64() < EARLY_TERMINAL_PROBABILITY {
+                    nodes.push(self.terminal(rng));
+                } else {
+                    nodes.push(self.medial(rng));
+                }
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (layer, indices) in layers.iter().enumerate() {
+            let later: Vec<usize> = layers[layer + 1..]
+                .iter()
+                .flatten()
+                .copied()
+                .collect();
+
+            for &from in indices {
+                if nodes[from].terminal() {
+                    continue;
+                }
+
+                let mut targets = later.clone();
+                rng.shuffle(&mut targets);
+                targets.truncate(rng.usize(1..=self.branching));
+                edges.extend(
+                    targets
+                        .into_iter()
+                        .map(|to| (from, to)),
+                );
+            }
+        }
+
+        Ok(Blueprint {
+            labels: (0..nodes.len())
+                .map(|i| i.to_string())
+                .collect(),
+            nodes,
+            edges,
+            source: 0,
+        })
+    }
+
+    /* HELPER METHODS */
+
+    fn medial(&self, rng: &mut Rng) -> Node {
+        Node::Medial(rng.usize(0..self.players))
+    }
+
+    fn terminal(&self, rng: &mut Rng) -> Node {
+        let utility = (0..self.players)
+            .map(|_| rng.i64(-self.payoff..=self.payoff))
+            .collect();
+
+        Node::Terminal(rng.usize(0..self.players), utility)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::Implicit;
+    use crate::solver::Game;
+
+    #[test]
+    fn generated_games_are_valid_and_reproducible() -> Result<()> {
+        for seed in 0..100 {
+            let generator = Generator::new(3).depth(5);
+            let a = generator.generate(&mut Rng::with_seed(seed))?;
+            let b = generator.generate(&mut Rng::with_seed(seed))?;
+            assert_eq!(a.edges, b.edges);
+
+            let session = a.session("random")?;
+            assert_eq!(session.players(), 3);
+
+            let mut stack = vec![session.source()];
+            while let Some(state) = stack.pop() {
+                assert!(Game::<3>::turn(&session, state) < 3);
+                let children = Implicit::adjacent(&session, state);
+                assert_eq!(children.is_empty(), session.sink(state));
+                stack.extend(children);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cannot_generate_degenerate_games() {
+        let mut rng = Rng::with_seed(0);
+        assert!(
+            Generator::new(0)
+                .generate(&mut rng)
+                .is_err()
+        );
+        assert!(
+            Generator::new(2)
+                .depth(0)
+                .generate(&mut rng)
+                .is_err()
+        );
+        assert!(
+            Generator::new(2)
+                .branching(0)
+                .generate(&mut rng)
+                .is_err()
+        );
+    }
+}
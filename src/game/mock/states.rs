@@ -0,0 +1,104 @@
+//! # Graph State Handling Module
+//!
+//! This module helps parse the string encoding of a graph game state, which is
+//! the identifier given to its node in the game file, into the representation
+//! used internally by a game session.
+
+use anyhow::Result;
+use petgraph::graph::NodeIndex;
+use regex::Regex;
+
+use crate::game::State;
+use crate::game::error::GameError;
+use crate::game::mock::NAME;
+use crate::game::mock::Session;
+
+/* GRAPH STATE ENCODING */
+
+pub const STATE_DEFAULT: &str = "start";
+pub const STATE_PATTERN: &str = r"^\S+$";
+pub const STATE_PROTOCOL: &str = "A state string should be the identifier of \
+one of the nodes declared in the game file, which is a non-empty string with \
+no whitespace. For example, 'start' refers to the node declared with the \
+identifier 'start'. Nodes which are not reachable from the source node of the \
+game cannot be referred to.";
+
+/* API */
+
+/// Returns the state corresponding to the node identified by `from` in the
+/// game graph of `session`. This does not verify that the node is reachable
+/// from the current source node of `session`.
+pub fn decode_state_string(session: &Session, from: String) -> Result<State> {
+    check_state_pattern(&from)?;
+    if let Some(index) = session
+        .labels
+        .iter()
+        .position(|label| *label == from)
+    {
+        Ok(Session::encode_index(NodeIndex::new(index)))
+    } else {
+        Err(GameError::StateMalformed {
+            game: NAME,
+            hint: format!(
+                "There is no node identified by '{from}' in the game '{}'.",
+                session.name,
+            ),
+        }
+        .into())
+    }
+}
+
+/* STATE STRING VERIFICATION */
+
+fn check_state_pattern(from: &String) -> Result<(), GameError> {
+    let re = Regex::new(STATE_PATTERN).unwrap();
+    if !re.is_match(from) {
+        Err(GameError::StateMalformed {
+            game: NAME,
+            hint: format!(
+                "Input string '{from}' does not match the pattern \
+                '{STATE_PATTERN}'.",
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/* TESTS */
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::game::*;
+
+    #[test]
+    fn state_pattern_is_valid_regex() {
+        assert!(Regex::new(STATE_PATTERN).is_ok());
+    }
+
+    #[test]
+    fn default_state_matches_state_pattern() {
+        let re = Regex::new(STATE_PATTERN).unwrap();
+        assert!(re.is_match(STATE_DEFAULT));
+    }
+
+    #[test]
+    fn node_identifiers_round_trip() -> Result<()> {
+        let session = Session::default();
+        for label in session.labels.clone() {
+            let state = decode_state_string(&session, label.clone())?;
+            assert_eq!(session.encode(state)?, label);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_or_malformed_states_fail_checks() {
+        let session = Session::default();
+        assert!(decode_state_string(&session, "".into()).is_err());
+        assert!(decode_state_string(&session, "two words".into()).is_err());
+        assert!(decode_state_string(&session, "nowhere".into()).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! # Graph Variant Handling Module
+//!
+//! This module helps parse the variant string provided to the graph game, which
+//! is the path to a game file, and the contents of that file into a session.
+//!
+//! Game files are JSON objects declaring the nodes and edges of a game graph,
+//! along with the node where the game begins. For example:
+//!
+//! ```none
+//! {
+//!     "name": "tiny",
+//!     "source": "a",
+//!     "nodes": [
+//!         { "id": "a", "turn": 0 },
+//!         { "id": "b", "turn": 1, "utility": [1, -1] },
+//!         { "id": "c", "turn": 1, "utility": [-1, 1] }
+//!     ],
+//!     "edges": [["a", "b"], ["a", "c"]]
+//! }
+//! ```
+
+use anyhow::Context;
+use anyhow::Result;
+use regex::Regex;
+use serde_json::Map;
+use serde_json::Value;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::game::Player;
+use crate::game::error::GameError;
+use crate::game::mock::Blueprint;
+use crate::game::mock::NAME;
+use crate::game::mock::Node;
+use crate::game::mock::Session;
+use crate::game::mock::states::STATE_PATTERN;
+use crate::solver::IUtility;
+
+/* GRAPH VARIANT ENCODING */
+
+pub const VARIANT_DEFAULT: &str = "example";
+pub const VARIANT_PATTERN: &str = r"^.+$";
+pub const VARIANT_PROTOCOL: &str = "The variant should be the path to a game \
+file, or 'example' for a small built-in game. A game file is a JSON object \
+with a 'source' field holding the identifier of the node where the game \
+begins, a 'nodes' field holding a list of nodes, and an 'edges' field holding \
+a list of pairs of node identifiers, each of which is a move from the first \
+node to the second. Games must be acyclic, so no sequence of moves may lead \
+back to a node it started from. Each node is an object with an 'id' field \
+holding its identifier (a non-empty string with no whitespace), a 'turn' field \
+holding the 0-indexed player whose turn it is at the node, and, only for \
+terminal nodes, a 'utility' field holding a list of integer utilities with one \
+entry per player. An optional 'name' field names the game, and defaults to the \
+file's name. Solutions are stored under that name, so different games should \
+have different names.";
+
+/// Contents of the game file of the default variant.
+const EXAMPLE: &str = include_str!("example.json");
+
+/* API */
+
+/// Returns a graph game session set up using the game file at the path given
+/// by `variant`, or the built-in example game if `variant` is the default.
+pub fn parse_variant(variant: String) -> Result<Session> {
+    check_variant_pattern(&variant)?;
+    let contents = if variant == VARIANT_DEFAULT {
+        EXAMPLE.to_owned()
+    } else {
+        fs::read_to_string(&variant)
+            .context(format!("Failed to read game file '{variant}'."))?
+    };
+
+    let stem = Path::new(&variant)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or(variant.clone());
+
+    let (name, blueprint) = parse_game(&contents, stem)?;
+    let mut session = blueprint
+        .session(&format!("{NAME}_{name}"))
+        .context("Game file does not describe a valid game graph.")?;
+
+    session.variant = variant;
+    session.name = name;
+    Ok(session)
+}
+
+/* GAME FILE PARSING */
+
+/// Returns the name of the game declared in `contents` (or `stem` if it does
+/// not declare one), along with the blueprint of its graph.
+fn parse_game(
+    contents: &str,
+    stem: String,
+) -> Result<(String, Blueprint), GameError> {
+    let root: Value = serde_json::from_str(contents)
+        .map_err(|e| malformed(format!("Game file is not valid JSON: {e}.")))?;
+
+    let root = root
+        .as_object()
+        .ok_or_else(|| malformed("Game file must hold a JSON object."))?;
+
+    let name = match root.get("name") {
+        None => stem,
+        Some(Value::String(name)) => name.clone(),
+        Some(_) => Err(malformed("The 'name' field must be a string."))?,
+    };
+
+    let mut nodes = Vec::new();
+    let mut labels = Vec::new();
+    let mut indices = HashMap::new();
+    for node in array(root, "nodes")? {
+        let node = node
+            .as_object()
+            .ok_or_else(|| malformed("Each node must be a JSON object."))?;
+
+        let (label, node) = parse_node(node)?;
+        if indices
+            .insert(label.clone(), nodes.len())
+            .is_some()
+        {
+            Err(malformed(format!(
+                "Node '{label}' is declared twice."
+            )))?
+        }
+
+        labels.push(label);
+        nodes.push(node);
+    }
+
+    let index = |value: &Value| -> Result<usize, GameError> {
+        let label = value
+            .as_str()
+            .ok_or_else(|| malformed("Node references must be strings."))?;
+
+        indices
+            .get(label)
+            .copied()
+            .ok_or_else(|| malformed(format!("Node '{label}' is undeclared.")))
+    };
+
+    let mut edges = Vec::new();
+    for edge in array(root, "edges")? {
+        match edge.as_array().map(Vec::as_slice) {
+            Some([from, to]) => edges.push((index(from)?, index(to)?)),
+            _ => Err(malformed(
+                "Each edge must be a pair of node identifiers.",
+            ))?,
+        }
+    }
+
+    let source = root
+        .get("source")
+        .ok_or_else(|| malformed("Game file is missing the 'source' field."))
+        .and_then(index)?;
+
+    Ok((
+        name,
+        Blueprint {
+            nodes,
+            labels,
+            edges,
+            source,
+        },
+    ))
+}
+
+/// Returns the identifier of the node declared by `node` along with the node.
+fn parse_node(node: &Map<String, Value>) -> Result<(String, Node), GameError> {
+    let re = Regex::new(STATE_PATTERN).unwrap();
+    let label = match node.get("id") {
+        Some(Value::String(id)) if re.is_match(id) => id.clone(),
+        _ => Err(malformed(
+            "Each node must have an 'id' field holding a non-empty string \
+            with no whitespace.",
+        ))?,
+    };
+
+    let turn = node
+        .get("turn")
+        .and_then(Value::as_u64)
+        .and_then(|t| Player::try_from(t).ok())
+        .ok_or_else(|| {
+            malformed(format!(
+                "Node '{label}' must have a 'turn' field holding a \
+                non-negative integer."
+            ))
+        })?;
+
+    match node.get("utility") {
+        None => Ok((label, Node::Medial(turn))),
+        Some(Value::Array(utility)) => {
+            let utility = utility
+                .iter()
+                .map(Value::as_i64)
+                .collect::<Option<Vec<IUtility>>>()
+                .ok_or_else(|| {
+                    malformed(format!(
+                        "The utility of node '{label}' must only contain \
+                        integers."
+                    ))
+                })?;
+
+            Ok((label, Node::Terminal(turn, utility)))
+        },
+        Some(_) => Err(malformed(format!(
+            "The 'utility' field of node '{label}' must be a list."
+        ))),
+    }
+}
+
+/// Returns the array held by the field `key` of `root`.
+fn array<'a>(
+    root: &'a Map<String, Value>,
+    key: &str,
+) -> Result<&'a Vec<Value>, GameError> {
+    root.get(key)
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            malformed(format!(
+                "Game file must have a '{key}' list field."
+            ))
+        })
+}
+
+/// Returns a variant error explained by `hint`.
+fn malformed(hint: impl Into<String>) -> GameError {
+    GameError::VariantMalformed {
+        game: NAME,
+        hint: hint.into(),
+    }
+}
+
+/* VARIANT STRING VERIFICATION */
+
+fn check_variant_pattern(variant: &str) -> Result<(), GameError> {
+    let re = Regex::new(VARIANT_PATTERN).unwrap();
+    if !re.is_match(variant) {
+        Err(malformed(format!(
+            "String does not match the pattern '{VARIANT_PATTERN}'.",
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/* TESTS */
+
+#[cfg(test)]
+mod test {
+
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::game::*;
+    use crate::test;
+    use crate::test::DevelopmentData;
+
+    /// Used for storing the game files parsed in tests under their own
+    /// subdirectory.
+    const MODULE_NAME: &str = "mock-variant-tests";
+
+    #[test]
+    fn variant_pattern_is_valid_regex() {
+        assert!(Regex::new(VARIANT_PATTERN).is_ok());
+    }
+
+    #[test]
+    fn default_variant_matches_variant_pattern() {
+        let re = Regex::new(VARIANT_PATTERN).unwrap();
+        assert!(re.is_match(VARIANT_DEFAULT));
+    }
+
+    #[test]
+    fn game_files_are_parsed_into_sessions() -> Result<()> {
+        let path = test::get_directory(
+            DevelopmentData::Files,
+            PathBuf::from(MODULE_NAME),
+        )?
+        .join("graph-variant-test.json");
+
+        fs::write(
+            &path,
+            r#"{
+                "source": "a",
+                "nodes": [
+                    { "id": "a", "turn": 0 },
+                    { "id": "b", "turn": 1 },
+                    { "id": "c", "turn": 2, "utility": [3, 2, 1] }
+                ],
+                "edges": [["a", "b"], ["b", "c"], ["a", "c"]]
+            }"#,
+        )?;
+
+        let session = Session::variant(path.to_string_lossy().into())?;
+
+        assert_eq!(session.players(), 3);
+        assert_eq!(session.name(), "graph-variant-test");
+        assert_eq!(session.encode(session.source())?, "a");
+
+        let children: Vec<String> =
+            Implicit::adjacent(&session, session.source())
+                .into_iter()
+                .map(|s| session.encode(s))
+                .collect::<Result<_>>()?;
+
+        assert_eq!(children.len(), 2);
+        assert!(children.contains(&"b".into()));
+        assert!(children.contains(&"c".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_game_files_fail_checks() {
+        let files = [
+            "[]",
+            "{ not json }",
+            r#"{ "source": "a", "nodes": [], "edges": [] }"#,
+            r#"{ "source": "a", "nodes": [{ "id": "a" }], "edges": [] }"#,
+            r#"{ "source": "a", "edges": [],
+                "nodes": [{ "id": "a", "turn": 0 }, { "id": "a", "turn": 1 }] }"#,
+            r#"{ "source": "a", "edges": [["a", "b"]],
+                "nodes": [{ "id": "a", "turn": 0 }] }"#,
+            r#"{ "source": "a", "edges": [["a"]],
+                "nodes": [{ "id": "a", "turn": 0 }] }"#,
+            r#"{ "source": "a", "edges": [["a", "b"]],
+                "nodes": [{ "id": "a", "turn": 0, "utility": "win" },
+                          { "id": "b", "turn": 0 }] }"#,
+            r#"{ "source": "a", "edges": [["a", "b"]],
+                "nodes": [{ "id": "a", "turn": 0 },
+                          { "id": "b", "turn": 0, "utility": [1.5] }] }"#,
+        ];
+
+        for file in files {
+            assert!(parse_game(file, "test".into()).is_err(), "{file}");
+        }
+    }
+
+    #[test]
+    fn invalid_game_graphs_fail_checks() -> Result<()> {
+        let (_, blueprint) = parse_game(
+            r#"{ "source": "a", "edges": [["a", "b"], ["b", "a"]],
+                "nodes": [{ "id": "a", "turn": 0 }, { "id": "b", "turn": 1 }] }"#,
+            "test".into(),
+        )?;
+
+        assert!(blueprint.session("test").is_err());
+
+        let (_, blueprint) = parse_game(
+            r#"{ "source": "a",
+                "edges": [["a", "b"], ["b", "a"], ["a", "t"], ["b", "w"]],
+                "nodes": [{ "id": "a", "turn": 0 }, { "id": "b", "turn": 1 },
+                          { "id": "t", "turn": 0, "utility": [-1, 1] },
+                          { "id": "w", "turn": 0, "utility": [1, -1] }] }"#,
+            "test".into(),
+        )?;
+
+        let error = blueprint
+            .session("test")
+            .err()
+            .context("Cyclic game was accepted.")?;
+
+        assert!(format!("{error:#}").contains("cycle"));
+        Ok(())
+    }
+}
@@ -8,22 +8,23 @@
 use anyhow::Context;
 use anyhow::Result;
 use clap::ValueEnum;
+use rusqlite::types::Value;
 
 /* UTILITY MODULES */
 
 #[cfg(test)]
 mod test;
 
+pub mod conformance;
 pub mod util;
 pub mod error;
+pub mod registry;
 
 /* GAME MODULES */
 
-#[cfg(test)]
-pub mod mock;
-
 pub mod zero_by;
 pub mod mnk;
+pub mod mock;
 
 /* TYPES */
 
@@ -42,9 +43,14 @@ pub type Player = usize;
 /// Count of the number of players in a game.
 pub type PlayerCount = Player;
 
+/// The largest number of players that routines generic over a player count
+/// can be dispatched to at runtime. See [`crate::players`]. Since each player
+/// count up to this one is compiled separately, raising it slows builds.
+pub const MAX_PLAYERS: PlayerCount = 16;
+
 /* DEFINITIONS */
 
-// Specifies the game offerings available through all interfaces.
+/// Specifies the game offerings available through all interfaces.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum GameModule {
     /// Abstract game played over sets of items.
@@ -52,6 +58,9 @@ pub enum GameModule {
 
     /// Generalized version of Tic-Tac-Toe.
     Mnk,
+
+    /// Arbitrary extensive-form game declared in a file.
+    Graph,
 }
 
 /// Contains useful data about a game.
@@ -115,13 +124,41 @@ pub struct GameData {
     pub state_default: &'static str,
 }
 
+/// A named, typed attribute of game states which can be computed from a state
+/// alone and stored alongside its solution. See [`Features`].
+///
+/// # Example
+///
+/// In the case of the sequential game [`mnk`], the number of symbols already
+/// placed on the board:
+///
+/// ```none
+/// * Name: "ply"
+/// * Datatype: "INTEGER"
+/// * Extract: |game, state| game.ply(state).into()
+/// ```
+pub struct Feature<G, const B: usize = DEFAULT_STATE_BYTES> {
+    /// Name of the feature, which is used as its column name in solution
+    /// tables and to select it through the CLI. Must be unique per game.
+    pub name: &'static str,
+
+    /// SQL datatype of the values produced by [`Feature::extract`].
+    pub datatype: &'static str,
+
+    /// Computes the value of this feature for a state of `G`.
+    pub extract: fn(&G, State<B>) -> Value,
+}
+
 /* INTERFACES */
 
+/// Provides general information about a game family.
 pub trait Information {
     /// Returns useful information about the game family. See [`GameData`].
     fn info() -> GameData;
 }
 
+/// Provides the game's states as an implicit graph, where edges are legal
+/// moves between them.
 pub trait Implicit<const B: usize = DEFAULT_STATE_BYTES> {
     /// Returns the collection of states adjacent to `state` in this graph.
     ///
@@ -192,6 +229,7 @@ pub trait Implicit<const B: usize = DEFAULT_STATE_BYTES> {
     fn sink(&self, state: State<B>) -> bool;
 }
 
+/// Provides conversions between game states and their string encodings.
 pub trait Codec<const B: usize = DEFAULT_STATE_BYTES> {
     /// Decodes a state [`String`] encoding into a bit-packed [`State<B>`].
     ///
@@ -245,6 +283,7 @@ pub trait Codec<const B: usize = DEFAULT_STATE_BYTES> {
     fn encode(&self, state: State<B>) -> Result<String>;
 }
 
+/// Provides the construction of game sessions from variant strings.
 pub trait Variable {
     /// Initializes a version of the underlying game as the specified `variant`.
     ///
@@ -278,6 +317,8 @@ pub trait Variable {
         Self: Sized;
 }
 
+/// Provides a way of advancing the starting state of a game session along a
+/// verified history of states.
 pub trait Forward<const B: usize = DEFAULT_STATE_BYTES>
 where
     Self: Information + Codec<B> + Implicit<B> + Sized,
@@ -360,3 +401,50 @@ where
         Ok(())
     }
 }
+
+/// Provides the features that can be computed for the states of a game.
+pub trait Features<const B: usize = DEFAULT_STATE_BYTES>
+where
+    Self: Sized,
+{
+    /// Returns all the features that can be computed for states of this game,
+    /// in the order in which they should appear in solution tables.
+    ///
+    /// # Example
+    ///
+    /// Using the game [`mnk`], whose features describe board positions:
+    ///
+    /// ```ignore
+    /// use crate::game::mnk;
+    ///
+    /// let game = <mnk::Session>::default();
+    /// let ply = <mnk::Session>::features()
+    ///     .into_iter()
+    ///     .find(|f| f.name == "ply")
+    ///     .unwrap();
+    ///
+    /// assert_eq!((ply.extract)(&game, game.source()), 0.into());
+    /// ```
+    fn features() -> Vec<Feature<Self, B>>;
+}
+
+/// Provides the parameters of a game session that are determined by its
+/// variant, and which are therefore only known at runtime.
+pub trait Parameters {
+    /// Returns the variant string this session was constructed over. See
+    /// [`Variable::variant`].
+    fn variant_string(&self) -> &str;
+
+    /// Returns the number of players in this session's variant.
+    fn player_count(&self) -> PlayerCount;
+}
+
+/* AUXILIARY IMPLEMENTATIONS */
+
+impl<G, const B: usize> Clone for Feature<G, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G, const B: usize> Copy for Feature<G, B> {}
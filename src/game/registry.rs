@@ -0,0 +1,179 @@
+//! # Game Registry Module
+//!
+//! This module provides a central listing of the game offerings available
+//! through all interfaces, associating each [`GameModule`] with the static
+//! information exposed by its implementation. Interfaces should prefer looking
+//! games up here over matching on [`GameModule`] themselves, so that adding a
+//! game only requires registering it in [`GAMES`].
+
+use anyhow::Result;
+
+use std::ops::RangeInclusive;
+
+use crate::game::Codec;
+use crate::game::DEFAULT_STATE_BYTES;
+use crate::game::Features;
+use crate::game::Forward;
+use crate::game::GameData;
+use crate::game::GameModule;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::MAX_PLAYERS;
+use crate::game::Parameters;
+use crate::game::PlayerCount;
+use crate::game::Variable;
+use crate::game::Variant;
+use crate::game::mnk;
+use crate::game::mnk::WIDE_STATE_BYTES;
+use crate::game::mock;
+use crate::game::zero_by;
+use crate::interface::session::Adapter;
+use crate::interface::session::DynSession;
+use crate::solver::Solvable;
+use crate::solver::Tabular;
+
+/* DEFINITIONS */
+
+/// Static information about a game offering.
+pub struct Entry {
+    /// The game module this entry describes.
+    pub module: GameModule,
+
+    /// Provider of the game's general information. See [`GameData`].
+    pub info: fn() -> GameData,
+
+    /// Player counts for which the game's variants can be solved.
+    pub players: RangeInclusive<PlayerCount>,
+
+    /// Whether the game's variants (other than its default variant) are paths
+    /// to game files, which are read when constructing sessions over them.
+    pub files: bool,
+
+    /// Constructor of a game session over a variant, or over the game's
+    /// default variant if none is provided.
+    pub session: fn(Option<Variant>) -> Result<Box<dyn DynSession>>,
+}
+
+/* REGISTRY */
+
+/// All available game offerings, in the order they should be listed.
+pub static GAMES: [Entry; 3] = [
+    Entry {
+        module: GameModule::ZeroBy,
+        info: zero_by::Session::info,
+        players: 1..=MAX_PLAYERS,
+        files: false,
+        session: session::<zero_by::Session, DEFAULT_STATE_BYTES>,
+    },
+    Entry {
+        module: GameModule::Mnk,
+        info: <mnk::Session>::info,
+        players: 2..=MAX_PLAYERS,
+        files: false,
+        session: mnk_session,
+    },
+    Entry {
+        module: GameModule::Graph,
+        info: mock::Session::info,
+        players: 1..=MAX_PLAYERS,
+        files: true,
+        session: session::<mock::Session, DEFAULT_STATE_BYTES>,
+    },
+];
+
+/* API */
+
+/// Returns the registry entry corresponding to `module`.
+pub fn entry(module: GameModule) -> &'static Entry {
+    GAMES
+        .iter()
+        .find(|e| e.module == module)
+        .expect("All game modules must be registered.")
+}
+
+/* HELPERS */
+
+/// Returns a session of the game `G` over `variant`, falling back to the
+/// game's default variant if none is provided.
+fn session<G, const B: usize>(
+    variant: Option<Variant>,
+) -> Result<Box<dyn DynSession>>
+where
+    G: Information
+        + Parameters
+        + Variable
+        + Codec<B>
+        + Implicit<B>
+        + Forward<B>
+        + Features<B>
+        + Tabular
+        + Solvable<B>
+        + Sync
+        + 'static,
+{
+    let variant = variant.unwrap_or_else(|| G::info().variant_default.into());
+    Ok(Box::new(Adapter(G::variant(variant)?)))
+}
+
+/// Returns a session of the m,n,k game over `variant` (or over the default
+/// variant if none is provided), using the smallest state encoding that fits
+/// the variant's board.
+fn mnk_session(variant: Option<Variant>) -> Result<Box<dyn DynSession>> {
+    let variant = variant.unwrap_or_else(|| {
+        <mnk::Session>::info()
+            .variant_default
+            .into()
+    });
+
+    if mnk::state_bytes(&variant) == DEFAULT_STATE_BYTES {
+        session::<mnk::Session, DEFAULT_STATE_BYTES>(Some(variant))
+    } else {
+        session::<mnk::Session<WIDE_STATE_BYTES>, WIDE_STATE_BYTES>(Some(
+            variant,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use clap::ValueEnum;
+
+    use super::*;
+
+    #[test]
+    fn every_game_module_is_registered_once() {
+        for module in GameModule::value_variants() {
+            let count = GAMES
+                .iter()
+                .filter(|e| e.module == *module)
+                .count();
+
+            assert_eq!(count, 1);
+        }
+        assert_eq!(GAMES.len(), GameModule::value_variants().len());
+    }
+
+    #[test]
+    fn registered_names_match_cli_names() {
+        for entry in GAMES.iter() {
+            let name = entry
+                .module
+                .to_possible_value()
+                .unwrap();
+
+            assert_eq!(name.get_name(), (entry.info)().name);
+        }
+    }
+
+    #[test]
+    fn sessions_are_constructed_over_default_variants() -> Result<()> {
+        for entry in GAMES.iter() {
+            let data = (entry.info)();
+            let session = (entry.session)(None)?;
+            assert_eq!(session.data().name, data.name);
+            (entry.session)(Some(data.variant_default.into()))?;
+        }
+        Ok(())
+    }
+}
@@ -19,7 +19,7 @@ use crate::test::*;
 
 /* IMPLEMENTATIONS */
 
-impl mock::Session<'_> {
+impl mock::Session {
     /// Creates an SVG visualization of the game graph in the visuals directory
     /// under the development data directory at the project root.
     pub fn visualize(&self, module: &str) -> Result<()> {
@@ -51,7 +51,7 @@ impl mock::Session<'_> {
     }
 }
 
-impl Display for mock::Session<'_> {
+impl Display for mock::Session {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -61,13 +61,13 @@ impl Display for mock::Session<'_> {
                 &[Config::EdgeNoLabel, Config::NodeNoLabel],
                 &|_, _| String::new(),
                 &|_, n| {
-                    let (_, node) = n;
+                    let (index, node) = n;
                     let mut attrs = String::new();
                     match node {
                         mock::Node::Medial(turn) => {
                             attrs += &format!("label=P{turn} ");
                             attrs += "style=filled  ";
-                            if self.source() == self.state(node).unwrap() {
+                            if mock::Session::index(self.source()) == index {
                                 attrs += "shape=doublecircle ";
                                 attrs += "fillcolor=navajowhite3 ";
                             } else {
@@ -7,40 +7,72 @@ use anyhow::Result;
 use anyhow::bail;
 use rusqlite::Connection;
 
-use std::env;
 use std::fmt::Display;
+use std::fs;
 
 use crate::game::GameData;
 use crate::game::Information;
 use crate::game::State;
 use crate::game::{Codec, Implicit, error::GameError};
 use crate::interface::GameAttribute;
+use crate::interface::config;
 
 /* DATABASE */
 
-/// Parses environment variables and establishes an SQLite connection to the
-/// global game solution database.
+/// Establishes an SQLite connection to the global game solution database at
+/// the location given by the configuration (see [`config`]), creating its
+/// parent directories if they do not exist.
 pub fn database() -> Result<Connection> {
-    let path = env::var("DATABASE")
-        .context("DATABASE environment variable not set.")?;
+    let config = config::current()
+        .context("Failed to resolve database configuration.")?;
+
+    let path = &config.database;
+    if let Some(dir) = path
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+    {
+        fs::create_dir_all(dir).context(format!(
+            "Failed to create database directory {}",
+            dir.display()
+        ))?;
+    }
 
-    let db = Connection::open(&path).context(format!(
+    let db = Connection::open(path).context(format!(
         "Failed to initialize SQLite connection to {}",
-        path
+        path.display()
     ))?;
 
-    db.execute(
-        "PRAGMA cache_size = 10000; \
-            PRAGMA synchronous = OFF; \
-            PRAGMA journal_mode = MEMORY; \
-            PRAGMA temp_store = MEMORY;",
-        [],
-    )
-    .context("Failed to tune SQLite database options.")?;
+    db.execute_batch(&config.pragma_query())
+        .context("Failed to tune SQLite database options.")?;
 
     Ok(db)
 }
 
+/// Returns the string prepended to the names of the solution tables of game
+/// sessions, as given by the configuration (see [`config`]).
+pub fn prefix() -> Result<&'static str> {
+    let config = config::current()
+        .context("Failed to resolve table prefix configuration.")?;
+
+    Ok(&config.prefix)
+}
+
+/* STATE CONVERSION */
+
+/// Returns the state made up of the bytes in `state`, which is how states are
+/// passed through [`DynSession`] trait objects.
+///
+/// [`DynSession`]: crate::interface::session::DynSession
+pub fn bytes<const B: usize>(state: &[u8]) -> Result<State<B>> {
+    match state.try_into() {
+        Ok(state) => Ok(state),
+        Err(_) => bail!(
+            "Expected a state of {B} bytes, but received {} bytes.",
+            state.len()
+        ),
+    }
+}
+
 /* STATE HISTORY VERIFICATION */
 
 /// Verifies that the elements of `history` are a valid sequence of states under
@@ -65,26 +97,10 @@ where
                     .decode(s)
                     .context(format!("Failed to parse line #{l}."))?;
 
-                if target.sink(prev) {
-                    bail!(
-                        terminal_history_error(target, prev, next)?.context(
-                            format!(
-                                "Invalid state transition found at line #{l}.",
-                            ),
-                        )
-                    )
-                }
-
-                let transitions = target.adjacent(prev);
-                if !transitions.contains(&next) {
-                    bail!(
-                        transition_history_error(target, prev, next)?.context(
-                            format!(
-                                "Invalid state transition found at line #{l}."
-                            ),
-                        )
-                    )
-                }
+                verify_transition(target, prev, next).context(format!(
+                    "Invalid state transition found at line #{l}."
+                ))?;
+
                 prev = next;
             }
             Ok(prev)
@@ -106,6 +122,30 @@ where
     }
 }
 
+/// Verifies that transitioning from `prev` to `next` is legal under the rules
+/// of `target`, failing if this is not true.
+pub fn verify_transition<const B: usize, G>(
+    target: &G,
+    prev: State<B>,
+    next: State<B>,
+) -> Result<()>
+where
+    G: Information + Implicit<B> + Codec<B>,
+{
+    if target.sink(prev) {
+        bail!(terminal_history_error(target, prev, next)?)
+    }
+
+    if !target
+        .adjacent(prev)
+        .contains(&next)
+    {
+        bail!(transition_history_error(target, prev, next)?)
+    }
+
+    Ok(())
+}
+
 /// Enumerates lines and trims whitespace from input.
 fn sanitize_input(mut input: Vec<String>) -> Vec<(usize, String)> {
     input
@@ -177,6 +217,7 @@ impl Display for GameAttribute {
 }
 
 impl GameData {
+    /// Returns the value of `attribute` in this game data.
     pub fn find(&self, attribute: GameAttribute) -> &str {
         match attribute {
             GameAttribute::VariantProtocol => self.variant_protocol,
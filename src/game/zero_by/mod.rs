@@ -12,7 +12,6 @@
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
-use anyhow::bail;
 use bitvec::array::BitArray;
 use bitvec::field::BitField;
 use bitvec::order::Msb0;
@@ -22,10 +21,13 @@ use rusqlite::Transaction;
 use rusqlite::params_from_iter;
 
 use crate::game::Codec;
+use crate::game::Feature;
+use crate::game::Features;
 use crate::game::Forward;
 use crate::game::GameData;
 use crate::game::Implicit;
 use crate::game::Information;
+use crate::game::Parameters;
 use crate::game::Player;
 use crate::game::PlayerCount;
 use crate::game::State;
@@ -40,7 +42,7 @@ use crate::solver::Queries;
 use crate::solver::SUtility;
 use crate::solver::SimpleUtility;
 use crate::solver::Solution;
-use crate::solver::algorithm::acyclic;
+use crate::solver::Tabular;
 use crate::solver::db::Schema;
 
 /* SUBMODULES */
@@ -65,7 +67,9 @@ elements than currently available in the set.";
 
 /* GAME IMPLEMENTATION */
 
+/// A session of a variant of Zero-By.
 pub struct Session {
+    variant: Variant,
     start_elems: Elements,
     start_state: State,
     player_bits: usize,
@@ -75,29 +79,6 @@ pub struct Session {
 }
 
 impl Session {
-    pub fn new(variant: Option<Variant>) -> Result<Self> {
-        if let Some(v) = variant {
-            Self::variant(v)
-        } else {
-            Ok(Self::default())
-        }
-    }
-
-    pub fn solve(&mut self, mode: IOMode) -> Result<()> {
-        match self.players {
-            1 => acyclic::solve::<1, 8, _>(self, mode),
-            2 => acyclic::solve::<2, 8, _>(self, mode),
-            3 => acyclic::solve::<3, 8, _>(self, mode),
-            4 => acyclic::solve::<4, 8, _>(self, mode),
-            5 => acyclic::solve::<5, 8, _>(self, mode),
-            6 => acyclic::solve::<6, 8, _>(self, mode),
-            7 => acyclic::solve::<7, 8, _>(self, mode),
-            8 => acyclic::solve::<8, 8, _>(self, mode),
-            9 => acyclic::solve::<9, 8, _>(self, mode),
-            _ => bail!("Provided player count is not implemented for zero-by."),
-        }
-    }
-
     /* UTILITY */
 
     fn encode_state(&self, turn: Player, elements: Elements) -> State {
@@ -142,6 +123,22 @@ impl Information for Session {
     }
 }
 
+impl Features for Session {
+    fn features() -> Vec<Feature<Self>> {
+        Vec::new()
+    }
+}
+
+impl Parameters for Session {
+    fn variant_string(&self) -> &str {
+        &self.variant
+    }
+
+    fn player_count(&self) -> PlayerCount {
+        self.players
+    }
+}
+
 impl Variable for Session {
     fn variant(variant: Variant) -> Result<Self> {
         parse_variant(variant).context("Malformed game variant.")
@@ -151,6 +148,10 @@ impl Variable for Session {
 impl Implicit for Session {
     fn adjacent(&self, state: State) -> Vec<State> {
         let (turn, elements) = self.decode_state(state);
+        if elements == 0 {
+            return Vec::new();
+        }
+
         let mut next = self
             .by
             .iter()
@@ -277,3 +278,30 @@ impl<const N: PlayerCount> Persistent<N> for Session {
         }
     }
 }
+
+impl Tabular for Session {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
+/* TESTS */
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::game::conformance;
+    use crate::game::conformance::Sum;
+
+    #[test]
+    fn session_conforms_to_game_contracts() -> Result<()> {
+        conformance::suite::<2, 8, Session>(&["", "2-7-1-2-3"], Sum::Zero)?;
+        conformance::suite::<3, 8, Session>(&["3-10-1-4"], Sum::Constant(-1))?;
+        conformance::suite::<5, 8, Session>(&["5-12-2-3"], Sum::Constant(-3))?;
+        conformance::suite::<12, 8, Session>(
+            &["12-30-1-2-3"],
+            Sum::Constant(-10),
+        )
+    }
+}
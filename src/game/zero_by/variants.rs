@@ -11,6 +11,7 @@ use regex::Regex;
 
 use crate::game::Player;
 use crate::game::error::GameError;
+use crate::game::util;
 use crate::game::zero_by::NAME;
 use crate::game::zero_by::Session;
 use crate::solver::db::SchemaBuilder;
@@ -50,12 +51,14 @@ pub fn parse_variant(variant: String) -> Result<Session> {
     let table = format!("{}_{}", NAME, variant);
     let schema = SchemaBuilder::new(&table)
         .players(players)
+        .prefix(util::prefix()?)
         .key("state", "INTEGER")
         .column("remoteness", "INTEGER")
         .column("player", "INTEGER")
         .build()?;
 
     Ok(Session {
+        variant,
         start_state: start_state.data,
         start_elems,
         player_bits,
@@ -109,7 +112,7 @@ fn check_param_count(params: &[u64]) -> Result<(), GameError> {
 }
 
 fn check_params_are_positive(params: &[u64]) -> Result<(), GameError> {
-    if params.iter().any(|&x| x == 0) {
+    if params.contains(&0) {
         Err(GameError::VariantMalformed {
             game: NAME,
             hint: "All integers in the string must be positive.".to_string(),
@@ -0,0 +1,188 @@
+//! # Search Benchmark Module
+//!
+//! This module measures how far the moves chosen by a Monte Carlo tree search
+//! are from perfect play, by comparing them against the solution table of a
+//! solved game variant over a random selection of its non-terminal states.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use fastrand::Rng;
+use rusqlite::Connection;
+use serde_json::Map;
+
+use crate::game;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::PlayerCount;
+use crate::interface::InfoFormat;
+use crate::solver::Game;
+use crate::solver::IUtility;
+use crate::solver::IntegerUtility;
+use crate::solver::algorithm::mcts;
+use crate::solver::db;
+use crate::solver::db::Schema;
+use crate::solver::features::Table;
+
+/* DEFINITIONS */
+
+/// Aggregate comparison between searched and perfect play.
+#[derive(Debug, Default, PartialEq)]
+struct Report {
+    /// Number of states at which a move was searched.
+    positions: usize,
+
+    /// Number of searched moves which preserve the mover's solved utility.
+    optimal: usize,
+
+    /// Sum over all searched moves of the utility the mover gives up relative
+    /// to perfect play.
+    loss: IUtility,
+
+    /// Largest utility given up by a single searched move.
+    worst: IUtility,
+}
+
+/* API */
+
+/// Compares the moves chosen by searches configured by `config` against the
+/// solution table of `game` described by `schema` at up to `positions`
+/// non-terminal states chosen at random (using `seed`, if provided), and
+/// prints a summary of the comparison to STDOUT in `format`.
+///
+/// # Errors
+///
+/// Fails if the game database is not available, or if there is no complete
+/// solution table for the game variant.
+pub fn mcts<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    config: mcts::Config,
+    positions: usize,
+    seed: Option<u64>,
+    format: InfoFormat,
+) -> Result<()>
+where
+    G: Information + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    let conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    let mut rng =
+        if let Some(seed) = seed { Rng::with_seed(seed) } else { Rng::new() };
+
+    let report =
+        compare::<N, B, G>(&conn, game, schema, &config, positions, &mut rng)?;
+
+    print!("{}", render(&report, format)?);
+    Ok(())
+}
+
+/* HELPERS */
+
+/// Returns the comparison of searched and perfect play at up to `positions`
+/// random non-terminal states of the table in `schema`.
+fn compare<const N: PlayerCount, const B: usize, G>(
+    conn: &Connection,
+    game: &G,
+    schema: &Schema,
+    config: &mcts::Config,
+    positions: usize,
+    rng: &mut Rng,
+) -> Result<Report>
+where
+    G: Information + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    if !db::table_exists(conn, schema.table())? {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    // Only terminal states have a remoteness of zero in solution tables.
+    let states = db::sample::<B>(
+        conn,
+        schema,
+        Some("remoteness > 0"),
+        positions,
+        rng,
+    )
+    .context("Failed to sample keys from solution table.")?;
+
+    let table = Table::new(conn, schema);
+    let mut report = Report::default();
+    for state in states {
+        let outcome = table.outcome(state)?;
+        let turn = outcome.player;
+        let chosen = mcts::choose::<N, B, G>(game, state, config, rng)
+            .context("Attempted to search from a terminal state.")?;
+
+        let loss = outcome.utility[turn] - table.outcome(chosen)?.utility[turn];
+        report.positions += 1;
+        report.optimal += (loss == 0) as usize;
+        report.loss += loss;
+        report.worst = report.worst.max(loss);
+    }
+
+    Ok(report)
+}
+
+/// Returns the string representation of `report` in the given `format`.
+fn render(report: &Report, format: InfoFormat) -> Result<String> {
+    let agreement = report.optimal as f64 / report.positions.max(1) as f64;
+    let mean = report.loss as f64 / report.positions.max(1) as f64;
+    match format {
+        InfoFormat::Legible => Ok(format!(
+            "positions: {}\noptimal: {}\nagreement: {agreement:.4}\n\
+            mean loss: {mean:.4}\nworst loss: {}\n",
+            report.positions, report.optimal, report.worst,
+        )),
+        InfoFormat::Json => {
+            let mut map = Map::new();
+            map.insert("positions".into(), report.positions.into());
+            map.insert("optimal".into(), report.optimal.into());
+            map.insert("agreement".into(), agreement.into());
+            map.insert("mean-loss".into(), mean.into());
+            map.insert("worst-loss".into(), report.worst.into());
+            let mut out = serde_json::to_string(&map)
+                .context("Failed to generate JSON object from report.")?;
+
+            out.push('\n');
+            Ok(out)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test;
+
+    #[test]
+    fn search_matches_solution_on_small_games() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("benchmark_test")?;
+        let report = compare::<2, 8, _>(
+            &conn,
+            &game,
+            &schema,
+            &mcts::Config::default(),
+            10,
+            &mut Rng::with_seed(2),
+        )?;
+
+        assert_eq!(
+            report,
+            Report {
+                positions: 4,
+                optimal: 4,
+                loss: 0,
+                worst: 0,
+            }
+        );
+        Ok(())
+    }
+}
@@ -13,10 +13,16 @@ use clap::Parser;
 use clap::Subcommand;
 
 use std::io::BufRead;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
 
 use crate::game::GameModule;
+use crate::game::Player;
+use crate::game::registry;
 use crate::interface::util;
-use crate::interface::{GameAttribute, InfoFormat};
+use crate::interface::{Estimator, Opponent, SamplePolicy};
+use crate::interface::{ExportFormat, GameAttribute, InfoFormat};
+use crate::solver::algorithm::mcts;
 use crate::{game::GameData, interface::IOMode};
 
 /* CLI DEFINITIONS */
@@ -34,6 +40,10 @@ pub struct Cli {
     /// Send no output to STDOUT.
     #[arg(short, long, group = "output")]
     pub quiet: bool,
+
+    /// Path to the game database, overriding the configured one.
+    #[arg(long, global = true)]
+    pub db: Option<PathBuf>,
 }
 
 /// Subcommand choices, specified as `nova <subcommand>`.
@@ -42,8 +52,41 @@ pub enum Commands {
     /// Build a dataset associated with a sequential game.
     Build(BuildArgs),
 
+    /// Compute features over the dataset associated with a solved game.
+    Featurize(FeaturizeArgs),
+
     /// Print information about the system's offering(s).
     Info(InfoArgs),
+
+    /// List all available games along with their metadata.
+    List(ListArgs),
+
+    /// Stream the dataset associated with a solved game to STDOUT.
+    Export(ExportArgs),
+
+    /// Look up the solved attributes of a game state.
+    Query(QueryArgs),
+
+    /// Play a game interactively against its solution.
+    Play(PlayArgs),
+
+    /// Generate a dataset of states sampled from playouts of a game.
+    Sample(SampleArgs),
+
+    /// Measure how far Monte Carlo tree search is from a game's solution.
+    Benchmark(BenchmarkArgs),
+
+    /// Check that the dataset associated with a solved game is consistent.
+    Verify(VerifyArgs),
+
+    /// Compare two datasets associated with the same solved game variant.
+    Diff(DiffArgs),
+
+    /// Print the graph of reachable states of a game in DOT format.
+    Graph(GraphArgs),
+
+    /// Serve game information and solutions through a local HTTP server.
+    Serve(ServeArgs),
 }
 
 /* ARGUMENT AND OPTION DEFINITIONS */
@@ -67,6 +110,36 @@ pub struct BuildArgs {
     /// Compute solution starting after a state history read from STDIN.
     #[arg(short, long)]
     pub forward: bool,
+
+    /// Compute the specified features after solving the game (defaults to
+    /// none of them; see `nova featurize`).
+    #[arg(long, value_delimiter = ',', num_args(1..))]
+    pub features: Vec<String>,
+}
+
+/// Arguments to the `nova featurize` subcommand.
+#[derive(Args)]
+pub struct FeaturizeArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Compute features for a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Compute only the specified features (defaults to all of them).
+    #[arg(short, long, value_delimiter = ',', num_args(1..))]
+    pub features: Vec<String>,
+
+    /// Recompute features for states that already have values for them.
+    #[arg(long)]
+    pub overwrite: bool,
+
+    /// Number of threads used to compute features.
+    #[arg(short, long, default_value_t = NonZeroUsize::MIN)]
+    pub threads: NonZeroUsize,
 }
 
 /// Arguments to the `nova info` subcommand.
@@ -85,6 +158,264 @@ pub struct InfoArgs {
     pub output: InfoFormat,
 }
 
+/// Arguments to the `nova list` subcommand.
+#[derive(Args)]
+pub struct ListArgs {
+    /* OPTIONAL ARGUMENTS */
+    /// Format in which to send output to STDOUT.
+    #[arg(short, long, default_value_t = InfoFormat::Legible)]
+    pub output: InfoFormat,
+}
+
+/// Arguments to the `nova export` subcommand.
+#[derive(Args)]
+pub struct ExportArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Export the dataset of a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Format in which to serialize the dataset's rows.
+    #[arg(short, long, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+
+    /// Write the dataset to a file instead of STDOUT.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments to the `nova query` subcommand.
+#[derive(Args)]
+pub struct QueryArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /// Encoding of the state to look up.
+    pub state: String,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Query a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Also look up every state reachable from the state in one move.
+    #[arg(short, long)]
+    pub children: bool,
+
+    /// Format in which to send output to STDOUT.
+    #[arg(short, long, default_value_t = InfoFormat::Legible)]
+    pub output: InfoFormat,
+}
+
+/// Arguments to the `nova play` subcommand.
+#[derive(Args)]
+pub struct PlayArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Play a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Players controlled by humans (defaults to the player moving first).
+    #[arg(long, value_delimiter = ',', num_args(1..))]
+    pub humans: Vec<Player>,
+
+    /// Who plays the moves of players not controlled by humans.
+    #[arg(long, default_value_t = Opponent::Solution)]
+    pub opponent: Opponent,
+
+    /// Number of search iterations per move of the MCTS opponent.
+    #[arg(long, default_value_t = mcts::Config::default().iterations)]
+    pub iterations: usize,
+
+    /// Exploration weight of the MCTS opponent.
+    #[arg(long, default_value_t = mcts::Config::default().exploration)]
+    pub exploration: f64,
+}
+
+/// Arguments to the `nova sample` subcommand.
+#[derive(Args)]
+pub struct SampleArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Sample states from a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Number of unique states to sample.
+    #[arg(short = 'n', long, default_value_t = 1000)]
+    pub count: usize,
+
+    /// How moves are chosen during playouts.
+    #[arg(short, long, default_value_t = SamplePolicy::Uniform)]
+    pub policy: SamplePolicy,
+
+    /// How the utility of each sampled state is estimated.
+    #[arg(short, long, default_value_t = Estimator::Rollout)]
+    pub estimator: Estimator,
+
+    /// Number of rollouts (or search iterations) used to estimate utilities.
+    #[arg(short, long, default_value_t = 0)]
+    pub rollouts: usize,
+
+    /// Seed for random choices, for reproducible datasets.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Compute only the specified features (defaults to all game features).
+    #[arg(long, value_delimiter = ',', num_args(1..))]
+    pub features: Vec<String>,
+
+    /// Number of threads used to compute features.
+    #[arg(short, long, default_value_t = NonZeroUsize::MIN)]
+    pub threads: NonZeroUsize,
+
+    /// Format in which to serialize the dataset's rows.
+    #[arg(short, long, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+
+    /// Write the dataset to a file instead of STDOUT.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments to the `nova benchmark` subcommand.
+#[derive(Args)]
+pub struct BenchmarkArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Benchmark against the solution of a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Maximum number of random non-terminal states to search from.
+    #[arg(short = 'n', long, default_value_t = 100)]
+    pub positions: usize,
+
+    /// Number of search iterations per move.
+    #[arg(long, default_value_t = mcts::Config::default().iterations)]
+    pub iterations: usize,
+
+    /// Exploration weight of the search.
+    #[arg(long, default_value_t = mcts::Config::default().exploration)]
+    pub exploration: f64,
+
+    /// Seed for random choices, for reproducible measurements.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Format in which to send output to STDOUT.
+    #[arg(short, long, default_value_t = InfoFormat::Legible)]
+    pub output: InfoFormat,
+}
+
+/// Arguments to the `nova verify` subcommand.
+#[derive(Args)]
+pub struct VerifyArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Verify the solution of a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Format in which to send output to STDOUT.
+    #[arg(short, long, default_value_t = InfoFormat::Legible)]
+    pub output: InfoFormat,
+}
+
+/// Arguments to the `nova diff` subcommand.
+#[derive(Args)]
+pub struct DiffArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Compare the datasets of a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Name of the first table (defaults to the variant's solution table).
+    #[arg(long)]
+    pub table: Option<String>,
+
+    /// Name of the second table (defaults to the first table's name).
+    #[arg(long)]
+    pub against_table: Option<String>,
+
+    /// Database file containing the second table (defaults to the game
+    /// database).
+    #[arg(long)]
+    pub against: Option<PathBuf>,
+
+    /// List every differing state instead of only a summary.
+    #[arg(long)]
+    pub full: bool,
+
+    /// Format in which to send output to STDOUT.
+    #[arg(short, long, default_value_t = InfoFormat::Legible)]
+    pub output: InfoFormat,
+}
+
+/// Arguments to the `nova graph` subcommand.
+#[derive(Args)]
+pub struct GraphArgs {
+    /* REQUIRED ARGUMENTS */
+    /// Target game name.
+    pub target: GameModule,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Print the graph of a specific variant of game.
+    #[arg(short, long)]
+    pub variant: Option<String>,
+
+    /// Maximum number of moves from the starting state to include (defaults
+    /// to every reachable state).
+    #[arg(short, long)]
+    pub depth: Option<usize>,
+
+    /// Start from the end of a state history read from STDIN.
+    #[arg(short, long)]
+    pub forward: bool,
+}
+
+/// Arguments to the `nova serve` subcommand.
+#[derive(Args)]
+pub struct ServeArgs {
+    /* DEFAULTS PROVIDED */
+    /// Loopback port on which to listen for requests.
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+
+    /* OPTIONAL ARGUMENTS */
+    /// Directory holding the game files that requests may name as variants of
+    /// games defined by files (no game files are read if not provided).
+    #[arg(long)]
+    pub games: Option<PathBuf>,
+
+    /// Allow web pages from any origin to read responses, by answering with a
+    /// wildcard CORS header.
+    #[arg(long)]
+    pub cors: bool,
+}
+
 /* STANDARD INPUT API */
 
 /// Parses STDIN into a line-by-line vector of its contents without any form of
@@ -117,3 +448,13 @@ pub fn format_and_output_game_attributes(
     print!("{out}");
     Ok(())
 }
+
+/// Prints the name, description, default variant, and supported player counts
+/// of every game in the registry to STDOUT in a specific `format`.
+pub fn format_and_output_game_listing(format: InfoFormat) -> Result<()> {
+    let out = util::format_game_listing(&registry::GAMES, format)
+        .context("Failed to format game listing.")?;
+
+    print!("{out}");
+    Ok(())
+}
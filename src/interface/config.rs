@@ -0,0 +1,378 @@
+//! # Configuration Module
+//!
+//! This module resolves the settings used to access the game database, which
+//! can come from several sources. The location of the database is taken from
+//! the first of the following that provides one:
+//!
+//! 1. The `--db` command line option (see [`init`]).
+//! 2. The `nova.toml` file in the working directory, or if there is none, the
+//!    one in the `nova` directory within the user's configuration directory
+//!    (e.g., `~/.config/nova/nova.toml` on Linux).
+//! 3. The `DATABASE` environment variable.
+//! 4. The file `nova.db` in the `nova` directory within the user's data
+//!    directory (e.g., `~/.local/share/nova/nova.db` on Linux).
+//!
+//! Configuration files may also provide a prefix for the names of all tables
+//! in the database, and override the SQLite PRAGMAs run on each connection.
+//! Every field is optional, and relative paths are resolved with respect to
+//! the directory of the file. For example:
+//!
+//! ```toml
+//! [database]
+//! path = "solutions.db"
+//! prefix = "experiment_"
+//!
+//! [database.pragmas]
+//! cache_size = 20000
+//! synchronous = "NORMAL"
+//! ```
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use regex::Regex;
+use toml::Table;
+use toml::Value;
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/* CONSTANTS */
+
+/// Name of configuration files.
+pub const CONFIG_FILE: &str = "nova.toml";
+
+/// Name of the environment variable holding the path to the game database.
+pub const DATABASE_VARIABLE: &str = "DATABASE";
+
+/// Name of the directory holding this project's files within the user's
+/// configuration and data directories.
+const DIRECTORY: &str = "nova";
+
+/// Name of the game database file within the user's data directory.
+const DATABASE_FILE: &str = "nova.db";
+
+/// PRAGMAs run on each connection to the game database unless overridden.
+const DEFAULT_PRAGMAS: [(&str, &str); 4] = [
+    ("cache_size", "10000"),
+    ("synchronous", "OFF"),
+    ("journal_mode", "MEMORY"),
+    ("temp_store", "MEMORY"),
+];
+
+/// Pattern that the names and values of PRAGMAs must match, which keeps them
+/// from being interpreted as anything but a single PRAGMA statement.
+const PRAGMA_PATTERN: &str = r"^-?[A-Za-z0-9_]+$";
+
+/* GLOBALS */
+
+/// Path to the game database provided through the command line, if any.
+static OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Configuration resolved on first use.
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/* DEFINITIONS */
+
+/// Settings used to access the game database.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Path to the game database file.
+    pub database: PathBuf,
+
+    /// String prepended to the names of all tables in the game database.
+    pub prefix: String,
+
+    /// Names and values of the PRAGMAs run on each database connection.
+    pub pragmas: Vec<(String, String)>,
+}
+
+/// The settings provided by a configuration file.
+#[derive(Default, Debug, PartialEq)]
+struct File {
+    database: Option<PathBuf>,
+    prefix: Option<String>,
+    pragmas: Vec<(String, String)>,
+}
+
+/* API */
+
+/// Makes `database` (if provided) take precedence over all other sources of
+/// the location of the game database. This has no effect if called after the
+/// configuration was first used, or if called more than once.
+pub fn init(database: Option<PathBuf>) {
+    if let Some(path) = database {
+        let _ = OVERRIDE.set(path);
+    }
+}
+
+/// Returns the configuration resolved from all sources, which is only done
+/// the first time this is called.
+///
+/// # Errors
+///
+/// Fails if a configuration file is malformed, or if no location for the game
+/// database is provided and the user's data directory cannot be determined.
+pub fn current() -> Result<&'static Config> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let file = if let Some(path) = locate() {
+        let contents = fs::read_to_string(&path).context(format!(
+            "Failed to read configuration file {}.",
+            path.display()
+        ))?;
+
+        let base = path
+            .parent()
+            .unwrap_or(Path::new(""));
+
+        parse(&contents, base).context(format!(
+            "Malformed configuration file {}.",
+            path.display()
+        ))?
+    } else {
+        File::default()
+    };
+
+    let config = resolve(
+        OVERRIDE.get().cloned(),
+        file,
+        env::var_os(DATABASE_VARIABLE),
+    )?;
+
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+impl Config {
+    /// Returns the SQL statements that set every PRAGMA in this configuration.
+    pub fn pragma_query(&self) -> String {
+        self.pragmas
+            .iter()
+            .map(|(name, value)| format!("PRAGMA {name} = {value};"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/* HELPERS */
+
+/// Returns the path to the configuration file with the highest precedence,
+/// if there is any.
+fn locate() -> Option<PathBuf> {
+    let local = PathBuf::from(CONFIG_FILE);
+    if local.is_file() {
+        return Some(local);
+    }
+
+    dirs::config_dir()
+        .map(|dir| {
+            dir.join(DIRECTORY)
+                .join(CONFIG_FILE)
+        })
+        .filter(|path| path.is_file())
+}
+
+/// Returns the configuration obtained by layering the `database` path from the
+/// command line, the contents of a configuration `file`, the value of the
+/// database environment variable `env`, and the defaults, in that order.
+fn resolve(
+    database: Option<PathBuf>,
+    file: File,
+    env: Option<OsString>,
+) -> Result<Config> {
+    let database = if let Some(path) = database.or(file.database) {
+        path
+    } else if let Some(path) = env.filter(|p| !p.is_empty()) {
+        PathBuf::from(path)
+    } else if let Some(dir) = dirs::data_dir() {
+        dir.join(DIRECTORY)
+            .join(DATABASE_FILE)
+    } else {
+        bail!(
+            "Could not determine the user's data directory. Try providing a \
+            database path through the --db option, a {CONFIG_FILE} file, or \
+            the {DATABASE_VARIABLE} environment variable."
+        )
+    };
+
+    let mut pragmas: Vec<(String, String)> = DEFAULT_PRAGMAS
+        .iter()
+        .map(|&(n, v)| (n.into(), v.into()))
+        .collect();
+
+    for (name, value) in file.pragmas {
+        if let Some(p) = pragmas
+            .iter_mut()
+            .find(|(n, _)| *n == name)
+        {
+            p.1 = value;
+        } else {
+            pragmas.push((name, value));
+        }
+    }
+
+    Ok(Config {
+        database,
+        prefix: file.prefix.unwrap_or_default(),
+        pragmas,
+    })
+}
+
+/// Parses the `contents` of a configuration file in the directory `base`.
+fn parse(contents: &str, base: &Path) -> Result<File> {
+    let root: Table = contents.parse()?;
+    let mut file = File::default();
+    for (key, value) in root {
+        match (key.as_str(), value) {
+            ("database", Value::Table(table)) => {
+                database(&mut file, table, base)?
+            },
+            (key, _) => bail!("Unexpected field '{key}'."),
+        }
+    }
+    Ok(file)
+}
+
+/// Parses the fields of the `database` section of a configuration file in the
+/// directory `base` into `file`.
+fn database(file: &mut File, table: Table, base: &Path) -> Result<()> {
+    let re = Regex::new(PRAGMA_PATTERN).unwrap();
+    for (key, value) in table {
+        match (key.as_str(), value) {
+            ("path", Value::String(path)) => {
+                file.database = Some(base.join(path));
+            },
+            ("prefix", Value::String(prefix)) => {
+                file.prefix = Some(prefix);
+            },
+            ("pragmas", Value::Table(pragmas)) => {
+                for (name, value) in pragmas {
+                    let value = match value {
+                        Value::String(s) => s,
+                        Value::Integer(i) => i.to_string(),
+                        Value::Boolean(b) => b.to_string(),
+                        _ => bail!(
+                            "The value of PRAGMA '{name}' must be a string, \
+                            integer, or boolean."
+                        ),
+                    };
+
+                    if !re.is_match(&name) || !re.is_match(&value) {
+                        Err(anyhow!(
+                            "PRAGMA '{name}' and its value '{value}' must \
+                            match the pattern '{PRAGMA_PATTERN}'."
+                        ))?
+                    }
+
+                    file.pragmas.push((name, value));
+                }
+            },
+            (key @ ("path" | "prefix" | "pragmas"), _) => {
+                bail!("Field 'database.{key}' has the wrong type.")
+            },
+            (key, _) => bail!("Unexpected field 'database.{key}'."),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn configuration_files_are_parsed() -> Result<()> {
+        let file = parse(
+            r#"
+            [database]
+            path = "solutions.db"
+            prefix = "experiment_"
+
+            [database.pragmas]
+            cache_size = -2000
+            synchronous = "NORMAL"
+            "#,
+            Path::new("/home/user"),
+        )?;
+
+        assert_eq!(
+            file.database,
+            Some("/home/user/solutions.db".into())
+        );
+        assert_eq!(file.prefix.as_deref(), Some("experiment_"));
+        assert_eq!(
+            file.pragmas,
+            vec![
+                ("cache_size".into(), "-2000".into()),
+                ("synchronous".into(), "NORMAL".into()),
+            ]
+        );
+
+        assert_eq!(parse("", Path::new(""))?, File::default());
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_configuration_files_fail_checks() {
+        let files = [
+            "not toml",
+            "database = 'nova.db'",
+            "[db]\npath = 'nova.db'",
+            "[database]\npath = 1",
+            "[database]\nsize = 1",
+            "[database.pragmas]\nsynchronous = 'OFF; DROP TABLE x'",
+            "[database.pragmas]\nsynchronous = 1.5",
+        ];
+
+        for contents in files {
+            assert!(
+                parse(contents, Path::new("")).is_err(),
+                "{contents}"
+            );
+        }
+    }
+
+    #[test]
+    fn sources_are_layered_by_precedence() -> Result<()> {
+        let file = || File {
+            database: Some("file.db".into()),
+            prefix: Some("x_".into()),
+            pragmas: vec![
+                ("synchronous".into(), "FULL".into()),
+                ("foreign_keys".into(), "ON".into()),
+            ],
+        };
+
+        let env = || Some(OsString::from("env.db"));
+        let config = resolve(Some("flag.db".into()), file(), env())?;
+        assert_eq!(config.database, PathBuf::from("flag.db"));
+        assert_eq!(config.prefix, "x_");
+
+        let config = resolve(None, file(), env())?;
+        assert_eq!(config.database, PathBuf::from("file.db"));
+        assert!(
+            config
+                .pragma_query()
+                .contains("PRAGMA synchronous = FULL;")
+        );
+        assert!(
+            config
+                .pragma_query()
+                .ends_with("PRAGMA foreign_keys = ON;")
+        );
+
+        let config = resolve(None, File::default(), env())?;
+        assert_eq!(config.database, PathBuf::from("env.db"));
+        assert_eq!(config.prefix, "");
+        assert_eq!(config.pragmas.len(), DEFAULT_PRAGMAS.len());
+        Ok(())
+    }
+}
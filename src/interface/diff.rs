@@ -0,0 +1,632 @@
+//! # Solution Diff Module
+//!
+//! This module compares two solution tables for the same game variant, which
+//! may live in different database files or under different table names. This
+//! is useful for finding out how the output of the system changes along with
+//! game implementations and solvers. States are matched by their primary key,
+//! and reported as missing (only in the first table), extra (only in the
+//! second table), or changed (present in both, but with different values in
+//! any column the tables have in common).
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use rusqlite::Connection;
+use rusqlite::OpenFlags;
+use rusqlite::types::Value;
+use rusqlite::types::ValueRef;
+use serde_json::Map;
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::game;
+use crate::game::Codec;
+use crate::game::Information;
+use crate::game::State;
+use crate::interface::InfoFormat;
+use crate::interface::util::json_value;
+use crate::interface::util::legible_value;
+use crate::solver::db;
+use crate::solver::db::Schema;
+
+/* DEFINITIONS */
+
+/// Locations of the two tables being compared, where unspecified parts refer
+/// to the solution table of the game variant in the game database.
+#[derive(Clone, Default)]
+pub struct Sides {
+    /// Name of the first table.
+    pub left: Option<String>,
+
+    /// Name of the second table, which defaults to that of the first one.
+    pub right: Option<String>,
+
+    /// Path to the database file containing the second table, which defaults
+    /// to the game database.
+    pub database: Option<PathBuf>,
+}
+
+/// How a state differs between the two tables.
+#[derive(Debug, PartialEq)]
+enum Status {
+    Missing,
+    Extra,
+    Changed,
+}
+
+/// A state whose entries differ between the two tables.
+struct Difference {
+    state: String,
+    status: Status,
+
+    /// Name of each common column whose value changed, along with its value
+    /// in the first and second table.
+    columns: Vec<(String, Value, Value)>,
+}
+
+/// The result of comparing two tables.
+#[derive(Default)]
+struct Comparison {
+    left: (String, usize),
+    right: (String, usize),
+    only_left: Vec<String>,
+    only_right: Vec<String>,
+    missing: usize,
+    extra: usize,
+    changed: usize,
+
+    /// Number of changed states per column.
+    columns: BTreeMap<String, usize>,
+    differences: Vec<Difference>,
+}
+
+/* API */
+
+/// Compares the two tables of `game` located by `sides` (see [`Sides`]),
+/// whose default is the table described by `schema`, and prints a summary of
+/// their differences to STDOUT in `format`. Every differing state is listed
+/// if `full` is true.
+///
+/// # Errors
+///
+/// Fails if either database is not available, if either table does not exist,
+/// if the tables cannot belong to the same game variant, or if `sides` locates
+/// the same table twice.
+pub fn tables<const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    sides: Sides,
+    full: bool,
+    format: InfoFormat,
+) -> Result<()>
+where
+    G: Information + Codec<B>,
+{
+    let left_conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    let left = sides
+        .left
+        .unwrap_or_else(|| schema.table().to_owned());
+
+    let right = sides
+        .right
+        .unwrap_or_else(|| left.clone());
+
+    let right_conn = if let Some(path) = &sides.database {
+        open(path)?
+    } else if left == right {
+        bail!("Cannot compare the table '{left}' against itself.")
+    } else {
+        game::util::database()
+            .context("Failed to obtain connection to game database.")?
+    };
+
+    let comparison = compare(
+        game,
+        schema.key(),
+        (&left_conn, &left),
+        (&right_conn, &right),
+        full,
+    )?;
+
+    print!("{}", render(&comparison, full, format)?);
+    Ok(())
+}
+
+/* HELPERS */
+
+/// Compares the table named `left.1` in `left.0` against the table named
+/// `right.1` in `right.0`, matching rows through their `key` column. Each
+/// differing state is recorded only if `full` is true.
+fn compare<const B: usize, G>(
+    game: &G,
+    key: &str,
+    left: (&Connection, &str),
+    right: (&Connection, &str),
+    full: bool,
+) -> Result<Comparison>
+where
+    G: Codec<B>,
+{
+    for (conn, table) in [left, right] {
+        if !db::table_exists(conn, table)? {
+            bail!("No table '{table}' was found in the database.")
+        }
+    }
+
+    check_compatible(key, left, right)?;
+
+    let left_cols = names(left.0, left.1)?;
+    let right_cols = names(right.0, right.1)?;
+    let common: Vec<String> = left_cols
+        .iter()
+        .filter(|c| *c != key && right_cols.contains(c))
+        .cloned()
+        .collect();
+
+    let mut out = Comparison {
+        left: (left.1.to_owned(), 0),
+        right: (right.1.to_owned(), 0),
+        only_left: left_cols
+            .iter()
+            .filter(|c| !right_cols.contains(c))
+            .cloned()
+            .collect(),
+        only_right: right_cols
+            .iter()
+            .filter(|c| !left_cols.contains(c))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    let query = |table: &str| {
+        let columns: Vec<String> = std::iter::once(key)
+            .chain(common.iter().map(String::as_str))
+            .map(|c| format!("\"{c}\""))
+            .collect();
+
+        format!(
+            "SELECT {} FROM {table} ORDER BY \"{key}\"",
+            columns.join(", ")
+        )
+    };
+
+    let mut left_stmt = left.0.prepare(&query(left.1))?;
+    let mut right_stmt = right.0.prepare(&query(right.1))?;
+    let mut left_rows = left_stmt.query([])?;
+    let mut right_rows = right_stmt.query([])?;
+    let read =
+        |row: Option<&rusqlite::Row>| -> Result<Option<(State<B>, Vec<Value>)>> {
+            if let Some(row) = row {
+                let key = db::state(row.get_ref(0)?)?;
+                let values = (1..=common.len())
+                    .map(|i| row.get(i))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Some((key, values)))
+            } else {
+                Ok(None)
+            }
+        };
+
+    let mut l = read(left_rows.next()?)?;
+    let mut r = read(right_rows.next()?)?;
+    loop {
+        let status = match (&l, &r) {
+            (None, None) => break,
+            (Some(_), None) => Status::Missing,
+            (None, Some(_)) => Status::Extra,
+            (Some((a, _)), Some((b, _))) => match db::order(a, b) {
+                Ordering::Less => Status::Missing,
+                Ordering::Greater => Status::Extra,
+                Ordering::Equal => Status::Changed,
+            },
+        };
+
+        let (key, columns) = match status {
+            Status::Missing => {
+                let (key, _) = l.take().unwrap();
+                out.left.1 += 1;
+                out.missing += 1;
+                l = read(left_rows.next()?)?;
+                (key, Vec::new())
+            },
+            Status::Extra => {
+                let (key, _) = r.take().unwrap();
+                out.right.1 += 1;
+                out.extra += 1;
+                r = read(right_rows.next()?)?;
+                (key, Vec::new())
+            },
+            Status::Changed => {
+                let (key, a) = l.take().unwrap();
+                let (_, b) = r.take().unwrap();
+                out.left.1 += 1;
+                out.right.1 += 1;
+                l = read(left_rows.next()?)?;
+                r = read(right_rows.next()?)?;
+                let columns: Vec<_> = common
+                    .iter()
+                    .zip(a.into_iter().zip(b))
+                    .filter(|(_, (a, b))| a != b)
+                    .map(|(c, (a, b))| (c.clone(), a, b))
+                    .collect();
+
+                if columns.is_empty() {
+                    continue;
+                }
+
+                out.changed += 1;
+                for (c, _, _) in &columns {
+                    *out.columns
+                        .entry(c.clone())
+                        .or_default() += 1;
+                }
+                (key, columns)
+            },
+        };
+
+        if full {
+            out.differences.push(Difference {
+                state: game
+                    .encode(key)
+                    .context("Failed to encode stored state.")?,
+                status,
+                columns,
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+/// Returns a read-only connection to the database file at `path`, which keeps
+/// a mistyped path from leaving an empty database behind.
+fn open(path: &Path) -> Result<Connection> {
+    if !path.is_file() {
+        bail!(
+            "No database file was found at {}.",
+            path.display()
+        )
+    }
+
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).context(
+        format!(
+            "Failed to initialize SQLite connection to {}",
+            path.display()
+        ),
+    )
+}
+
+/// Fails if the tables named `left.1` in `left.0` and `right.1` in `right.0`
+/// cannot hold solutions of the same game variant, which is the case if the
+/// datatypes of their `key` columns or their numbers of utility columns differ.
+fn check_compatible(
+    key: &str,
+    left: (&Connection, &str),
+    right: (&Connection, &str),
+) -> Result<()> {
+    let shape = |(conn, table): (&Connection, &str)| -> Result<_> {
+        let columns = db::columns(conn, table)?;
+        let datatype = columns
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, data)| data.clone())
+            .context(format!("Table '{table}' has no '{key}' column."))?;
+
+        let players = columns
+            .iter()
+            .filter(|(name, _)| name.starts_with("utility_"))
+            .count();
+
+        Ok((datatype, players))
+    };
+
+    let (left_key, left_players) = shape(left)?;
+    let (right_key, right_players) = shape(right)?;
+    if left_key != right_key {
+        bail!(
+            "Table '{}' has '{key}' keys of type {left_key}, but table '{}' has \
+            keys of type {right_key}, so they cannot belong to the same game \
+            variant.",
+            left.1,
+            right.1,
+        )
+    }
+
+    if left_players != right_players {
+        bail!(
+            "Table '{}' has {left_players} utility columns, but table '{}' has \
+            {right_players}, so they cannot belong to the same game variant.",
+            left.1,
+            right.1,
+        )
+    }
+
+    Ok(())
+}
+
+/// Returns the names of the columns of `table` in `conn`.
+fn names(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    Ok(db::columns(conn, table)?
+        .into_iter()
+        .map(|(n, _)| n)
+        .collect())
+}
+
+/// Returns the string representation of `comparison` in the given `format`,
+/// including each differing state if `full` is true.
+fn render(
+    comparison: &Comparison,
+    full: bool,
+    format: InfoFormat,
+) -> Result<String> {
+    let c = comparison;
+    match format {
+        InfoFormat::Legible => {
+            let mut out = format!(
+                "left: {} ({} rows)\nright: {} ({} rows)\nmissing: {}\n\
+                extra: {}\nchanged: {}\n",
+                c.left.0,
+                c.left.1,
+                c.right.0,
+                c.right.1,
+                c.missing,
+                c.extra,
+                c.changed,
+            );
+
+            for (column, count) in &c.columns {
+                out += &format!("\t{column}: {count}\n");
+            }
+
+            if !c.only_left.is_empty() {
+                out += &format!(
+                    "columns only in left: {}\n",
+                    c.only_left.join(", ")
+                );
+            }
+
+            if !c.only_right.is_empty() {
+                out += &format!(
+                    "columns only in right: {}\n",
+                    c.only_right.join(", ")
+                );
+            }
+
+            if full {
+                for d in &c.differences {
+                    out += &format!("\n{} ({})\n", d.state, d.status);
+                    for (column, a, b) in &d.columns {
+                        out += &format!(
+                            "\t{column}: {} -> {}\n",
+                            legible_value(a.into()),
+                            legible_value(b.into()),
+                        );
+                    }
+                }
+            }
+            Ok(out)
+        },
+        InfoFormat::Json => {
+            let side = |(table, rows): &(String, usize)| {
+                let mut map = Map::new();
+                map.insert("table".into(), table.clone().into());
+                map.insert("rows".into(), (*rows).into());
+                serde_json::Value::Object(map)
+            };
+
+            let mut map = Map::new();
+            map.insert("left".into(), side(&c.left));
+            map.insert("right".into(), side(&c.right));
+            map.insert("missing".into(), c.missing.into());
+            map.insert("extra".into(), c.extra.into());
+            map.insert("changed".into(), c.changed.into());
+            map.insert(
+                "columns".into(),
+                c.columns
+                    .iter()
+                    .map(|(k, v)| (k.clone(), (*v).into()))
+                    .collect::<Map<_, _>>()
+                    .into(),
+            );
+            map.insert(
+                "left-only-columns".into(),
+                c.only_left.clone().into(),
+            );
+            map.insert(
+                "right-only-columns".into(),
+                c.only_right.clone().into(),
+            );
+
+            if full {
+                let differences = c
+                    .differences
+                    .iter()
+                    .map(|d| {
+                        let mut map = Map::new();
+                        map.insert("state".into(), d.state.clone().into());
+                        map.insert(
+                            "status".into(),
+                            d.status.to_string().into(),
+                        );
+                        let columns = d
+                            .columns
+                            .iter()
+                            .map(|(name, a, b)| {
+                                let pair = vec![
+                                    json_value(ValueRef::from(a)),
+                                    json_value(ValueRef::from(b)),
+                                ];
+                                (name.clone(), pair.into())
+                            })
+                            .collect::<Map<_, _>>();
+
+                        map.insert("columns".into(), columns.into());
+                        serde_json::Value::Object(map)
+                    })
+                    .collect::<Vec<_>>();
+
+                map.insert("differences".into(), differences.into());
+            }
+
+            let mut out = serde_json::to_string(&map)
+                .context("Failed to generate JSON object from comparison.")?;
+
+            out.push('\n');
+            Ok(out)
+        },
+    }
+}
+
+/* AUXILIARY IMPLEMENTATIONS */
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Missing => write!(f, "missing"),
+            Status::Extra => write!(f, "extra"),
+            Status::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rusqlite::params;
+
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::game::Variable;
+    use crate::game::zero_by;
+    use crate::solver::db::SchemaBuilder;
+    use crate::test;
+    use crate::test::DevelopmentData;
+
+    /// Used for storing the database files touched in tests under their own
+    /// subdirectory.
+    const MODULE_NAME: &str = "diff-tests";
+
+    /// Creates a table named `table` through `conn` holding the given rows of
+    /// 2-player zero-by states, with their remoteness and utility.
+    fn table(
+        conn: &Connection,
+        game: &zero_by::Session,
+        table: &str,
+        rows: &[(&str, i64, [i64; 2])],
+    ) -> Result<()> {
+        let schema = SchemaBuilder::new(table)
+            .players(2)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        for (state, remoteness, utility) in rows {
+            let key = i64::from_be_bytes(game.decode((*state).into())?);
+            conn.execute(
+                &schema.insert_query(),
+                params![key, remoteness, utility[0], utility[1]],
+            )?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn differences_are_classified() -> Result<()> {
+        let conn = test::database()?;
+        let game = zero_by::Session::variant("2-3-1-2".into())?;
+        table(
+            &conn,
+            &game,
+            "diff_test_left",
+            &[
+                ("3-0", 2, [1, -1]),
+                ("2-1", 2, [-1, 1]),
+                ("1-1", 1, [1, -1]),
+            ],
+        )?;
+        table(
+            &conn,
+            &game,
+            "diff_test_right",
+            &[
+                ("3-0", 2, [1, -1]),
+                ("2-1", 3, [1, -1]),
+                ("0-0", 0, [1, -1]),
+            ],
+        )?;
+
+        let c = compare(
+            &game,
+            "state",
+            (&conn, "diff_test_left"),
+            (&conn, "diff_test_right"),
+            true,
+        )?;
+
+        assert_eq!((c.missing, c.extra, c.changed), (1, 1, 1));
+        assert_eq!(c.columns.get("remoteness"), Some(&1));
+        assert_eq!(c.columns.get("utility_0"), Some(&1));
+        assert_eq!(c.differences.len(), 3);
+
+        let changed = c
+            .differences
+            .iter()
+            .find(|d| d.status == Status::Changed)
+            .context("No changed state was reported.")?;
+
+        assert_eq!(changed.state, "2-1");
+        assert_eq!(changed.columns.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_tables_are_not_compared() -> Result<()> {
+        let conn = test::database()?;
+        let game = zero_by::Session::variant("2-3-1-2".into())?;
+        table(
+            &conn,
+            &game,
+            "diff_test_two",
+            &[("3-0", 2, [1, -1])],
+        )?;
+        let schema = SchemaBuilder::new("diff_test_three")
+            .players(3)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        let c = compare(
+            &game,
+            "state",
+            (&conn, "diff_test_two"),
+            (&conn, "diff_test_three"),
+            false,
+        );
+
+        assert!(c.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_database_files_are_not_created() -> Result<()> {
+        let path = test::get_directory(
+            DevelopmentData::Files,
+            PathBuf::from(MODULE_NAME),
+        )?
+        .join("missing.db");
+
+        assert!(open(&path).is_err());
+        assert!(!path.exists());
+        Ok(())
+    }
+}
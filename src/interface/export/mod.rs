@@ -0,0 +1,296 @@
+//! # Dataset Export Module
+//!
+//! This module provides routines for streaming the rows of a solution table
+//! out of the game database in formats that other tools can readily consume,
+//! translating state keys back into their human-readable string encodings.
+//!
+//! Exporting to Apache Parquet is available through the `parquet` feature.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use rusqlite::Connection;
+use rusqlite::types::ValueRef;
+use serde_json::Map;
+use serde_json::Value;
+
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use crate::game;
+use crate::game::Codec;
+use crate::game::Features;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::interface::ExportFormat;
+use crate::interface::util::hex;
+use crate::interface::util::json_value;
+use crate::solver::db;
+use crate::solver::db::Schema;
+
+/* SUBMODULES */
+
+#[cfg(feature = "parquet")]
+mod parquet;
+
+/* API */
+
+/// Streams every row of the solution table described by `schema` for the game
+/// `variant` into the file at `output` (or to STDOUT if it is not provided) in
+/// the specified `format`, encoding states through `game`'s [`Codec`].
+///
+/// # Errors
+///
+/// Fails if the game database is not available, if there is no solution table
+/// for the game variant, or if a stored state cannot be encoded by `game`.
+pub fn dataset<const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    variant: &str,
+    format: ExportFormat,
+    output: Option<&Path>,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B> + Features<B>,
+{
+    let conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    if !db::table_exists(&conn, schema.table())? {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    table(&conn, game, schema, variant, format, output)
+}
+
+/// Streams every row of the table described by `schema` from `conn` into the
+/// file at `output` (or to STDOUT if it is not provided) in the specified
+/// `format`, encoding states through `game`'s [`Codec`]. This allows tables
+/// outside of the game database to be written like solution tables.
+///
+/// # Errors
+///
+/// Fails if the table cannot be read, or if a stored state cannot be encoded
+/// by `game`.
+#[cfg_attr(not(feature = "parquet"), allow(unused_variables))]
+pub fn table<const B: usize, G>(
+    conn: &Connection,
+    game: &G,
+    schema: &Schema,
+    variant: &str,
+    format: ExportFormat,
+    output: Option<&Path>,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B> + Features<B>,
+{
+    let sink: Box<dyn Write + Send> = if let Some(path) = output {
+        let file = File::create(path).context(format!(
+            "Failed to create output file at {}.",
+            path.display()
+        ))?;
+        Box::new(file)
+    } else {
+        Box::new(io::stdout())
+    };
+
+    let mut out = BufWriter::new(sink);
+    let result = match format {
+        ExportFormat::Csv | ExportFormat::Jsonl => {
+            write_rows(conn, game, schema, format, &mut out)
+        },
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => {
+            parquet::write_rows(conn, game, schema, variant, &mut out)
+        },
+    }
+    .and_then(|_| Ok(out.flush()?));
+
+    match result {
+        Err(e) if closed_pipe(&e) => Ok(()),
+        other => other.context("Failed to write exported rows."),
+    }
+}
+
+/* HELPERS */
+
+/// Writes every row of the table in `schema` from `conn` into `out` according
+/// to the line-oriented `format`, replacing the primary key by the state's
+/// string encoding.
+fn write_rows<const B: usize, G, W>(
+    conn: &Connection,
+    game: &G,
+    schema: &Schema,
+    format: ExportFormat,
+    out: &mut W,
+) -> Result<()>
+where
+    G: Information + Codec<B>,
+    W: Write,
+{
+    let mut stmt = conn
+        .prepare(&schema.select_all_query())
+        .context("Failed to prepare table scan query.")?;
+
+    let names: Vec<String> = stmt
+        .column_names()
+        .iter()
+        .map(|&n| n.to_owned())
+        .collect();
+
+    let key = names
+        .iter()
+        .position(|n| n == schema.key())
+        .context("Solution table does not contain its primary key column.")?;
+
+    if let ExportFormat::Csv = format {
+        let header: Vec<String> = names
+            .iter()
+            .map(|n| csv_field(n))
+            .collect();
+
+        writeln!(out, "{}", header.join(","))?;
+    }
+
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let state = db::state::<B>(row.get_ref(key)?)?;
+        let encoding = game
+            .encode(state)
+            .context("Failed to encode stored state.")?;
+
+        match format {
+            ExportFormat::Csv => {
+                let mut fields = Vec::with_capacity(names.len());
+                for i in 0..names.len() {
+                    if i == key {
+                        fields.push(csv_field(&encoding));
+                    } else {
+                        fields.push(csv_value(row.get_ref(i)?));
+                    }
+                }
+                writeln!(out, "{}", fields.join(","))?;
+            },
+            ExportFormat::Jsonl => {
+                let mut map = Map::new();
+                for (i, name) in names.iter().enumerate() {
+                    if i == key {
+                        map.insert(
+                            name.clone(),
+                            Value::String(encoding.clone()),
+                        );
+                    } else {
+                        map.insert(name.clone(), json_value(row.get_ref(i)?));
+                    }
+                }
+                writeln!(out, "{}", Value::Object(map))?;
+            },
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => {
+                bail!("Parquet is not a line-oriented format.")
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns true if `error` was caused by the reading end of STDOUT closing,
+/// which is expected when the output is piped into a program like `head`.
+fn closed_pipe(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<io::Error>()
+        .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+}
+
+/// Returns the CSV representation of an arbitrary database value.
+fn csv_value(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => String::new(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => f.to_string(),
+        ValueRef::Text(t) => csv_field(&String::from_utf8_lossy(t)),
+        ValueRef::Blob(b) => hex(b),
+    }
+}
+
+/// Quotes `field` according to RFC 4180 if it contains any characters that
+/// would otherwise break the structure of a CSV record.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rusqlite::params;
+
+    use super::*;
+    use crate::game::Implicit;
+    use crate::game::mnk;
+    use crate::solver::db::SchemaBuilder;
+    use crate::test;
+
+    #[test]
+    fn csv_fields_are_quoted_when_necessary() {
+        assert_eq!(csv_field("10-0"), "10-0");
+        assert_eq!(csv_field("[[X, O]]"), "\"[[X, O]]\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn exported_rows_contain_encoded_states() -> Result<()> {
+        let conn = test::database()?;
+        let game = mnk::Session::default();
+        let schema = SchemaBuilder::new("export_test")
+            .players(2)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        conn.execute(
+            &schema.insert_query(),
+            params![i64::from_be_bytes(game.source()), 9, 0, 0],
+        )?;
+
+        let mut csv = Vec::new();
+        write_rows(&conn, &game, &schema, ExportFormat::Csv, &mut csv)?;
+        let csv = String::from_utf8(csv)?;
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("state,remoteness,utility_0,utility_1")
+        );
+
+        assert_eq!(
+            lines.next(),
+            Some("\"[[_, _, _], [_, _, _], [_, _, _]]\",9,0,0")
+        );
+
+        let mut jsonl = Vec::new();
+        write_rows(
+            &conn,
+            &game,
+            &schema,
+            ExportFormat::Jsonl,
+            &mut jsonl,
+        )?;
+        let row: Value = serde_json::from_slice(&jsonl)?;
+        assert_eq!(row["state"], "[[_, _, _], [_, _, _], [_, _, _]]");
+        assert_eq!(row["remoteness"], 9);
+        Ok(())
+    }
+}
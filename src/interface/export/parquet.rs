@@ -0,0 +1,344 @@
+//! # Parquet Export Module
+//!
+//! This module provides an Apache Arrow-backed exporter that writes the rows
+//! of a solution table into a Parquet file, typing each column according to
+//! its declared SQL datatype and annotating the file with metadata about the
+//! game variant it came from.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use arrow_array::ArrayRef;
+use arrow_array::RecordBatch;
+use arrow_array::builder::BinaryBuilder;
+use arrow_array::builder::Float64Builder;
+use arrow_array::builder::Int64Builder;
+use arrow_array::builder::StringBuilder;
+use arrow_schema::DataType;
+use arrow_schema::Field;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+use rusqlite::Connection;
+use rusqlite::types::ValueRef;
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::game::Codec;
+use crate::game::Features;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::interface::featurize;
+use crate::solver::db;
+use crate::solver::db::Schema;
+
+/* CONSTANTS */
+
+/// Maximum number of rows buffered in memory before being flushed into the
+/// output file as a single record batch.
+const BATCH_ROWS: usize = 1 << 16;
+
+/* DEFINITIONS */
+
+/// Accumulates the values of a single typed column of a record batch.
+enum ColumnBuilder {
+    Integer(Int64Builder),
+    Real(Float64Builder),
+    Text(StringBuilder),
+    Blob(BinaryBuilder),
+}
+
+/* API */
+
+/// Writes every row of the table in `schema` from `conn` into `out` as a
+/// Parquet file, replacing the primary key by the state's string encoding and
+/// recording the game, `variant`, and Nova version in the file's metadata.
+/// Columns are typed as declared in `schema`, or by the features of `game`
+/// for columns added to the table when computing them.
+pub fn write_rows<const B: usize, G, W>(
+    conn: &Connection,
+    game: &G,
+    schema: &Schema,
+    variant: &str,
+    out: W,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B> + Features<B>,
+    W: Write + Send,
+{
+    let mut stmt = conn
+        .prepare(&schema.select_all_query())
+        .context("Failed to prepare table scan query.")?;
+
+    let names: Vec<String> = stmt
+        .column_names()
+        .iter()
+        .map(|&n| n.to_owned())
+        .collect();
+
+    let features = featurize::datatypes::<B, G>();
+    let declared: Vec<(&str, &str)> = schema
+        .columns()
+        .into_iter()
+        .chain(features)
+        .collect();
+
+    let mut fields = Vec::with_capacity(names.len());
+    for name in names.iter() {
+        let data = if name == schema.key() {
+            DataType::Utf8
+        } else if let Some((_, data)) = declared
+            .iter()
+            .find(|(n, _)| n == name)
+        {
+            arrow_type(data)?
+        } else {
+            bail!(
+                "Column '{name}' is neither part of the table's schema nor a \
+                feature of the game."
+            )
+        };
+        fields.push(Field::new(name, data, true));
+    }
+
+    let metadata = HashMap::from([
+        ("nova.game".to_owned(), G::info().name.to_owned()),
+        ("nova.variant".to_owned(), variant.to_owned()),
+        (
+            "nova.version".to_owned(),
+            env!("CARGO_PKG_VERSION").to_owned(),
+        ),
+    ]);
+
+    let arrow = Arc::new(arrow_schema::Schema::new_with_metadata(
+        fields,
+        metadata.clone(),
+    ));
+
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_key_value_metadata(Some(
+            metadata
+                .into_iter()
+                .map(|(k, v)| KeyValue::new(k, v))
+                .collect(),
+        ))
+        .build();
+
+    let mut writer = ArrowWriter::try_new(out, arrow.clone(), Some(properties))
+        .context("Failed to initialize Parquet writer.")?;
+
+    let key = names
+        .iter()
+        .position(|n| n == schema.key())
+        .context("Solution table does not contain its primary key column.")?;
+
+    let mut builders = builders(&arrow);
+    let mut buffered = 0;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        for (i, builder) in builders.iter_mut().enumerate() {
+            if i == key {
+                let state = db::state::<B>(row.get_ref(key)?)?;
+                let encoding = game
+                    .encode(state)
+                    .context("Failed to encode stored state.")?;
+
+                builder.append(ValueRef::Text(encoding.as_bytes()))?;
+            } else {
+                builder.append(row.get_ref(i)?)?;
+            }
+        }
+
+        buffered += 1;
+        if buffered == BATCH_ROWS {
+            writer.write(&batch(&arrow, &mut builders)?)?;
+            buffered = 0;
+        }
+    }
+
+    if buffered > 0 {
+        writer.write(&batch(&arrow, &mut builders)?)?;
+    }
+
+    writer
+        .close()
+        .context("Failed to finalize Parquet file.")?;
+
+    Ok(())
+}
+
+/* HELPERS */
+
+/// Returns the Arrow datatype corresponding to the SQL datatype `data`.
+fn arrow_type(data: &str) -> Result<DataType> {
+    match &data.to_ascii_uppercase()[..] {
+        "INTEGER" => Ok(DataType::Int64),
+        "REAL" => Ok(DataType::Float64),
+        "TEXT" => Ok(DataType::Utf8),
+        "BLOB" => Ok(DataType::Binary),
+        other => bail!("SQL datatype '{other}' has no Arrow equivalent."),
+    }
+}
+
+/// Returns one empty column builder for each field in `schema`.
+fn builders(schema: &arrow_schema::Schema) -> Vec<ColumnBuilder> {
+    schema
+        .fields()
+        .iter()
+        .map(|f| match f.data_type() {
+            DataType::Int64 => ColumnBuilder::Integer(Int64Builder::new()),
+            DataType::Float64 => ColumnBuilder::Real(Float64Builder::new()),
+            DataType::Binary => ColumnBuilder::Blob(BinaryBuilder::new()),
+            _ => ColumnBuilder::Text(StringBuilder::new()),
+        })
+        .collect()
+}
+
+/// Drains `builders` into a record batch conforming to `schema`.
+fn batch(
+    schema: &Arc<arrow_schema::Schema>,
+    builders: &mut [ColumnBuilder],
+) -> Result<RecordBatch> {
+    let columns: Vec<ArrayRef> = builders
+        .iter_mut()
+        .map(ColumnBuilder::finish)
+        .collect();
+
+    RecordBatch::try_new(schema.clone(), columns)
+        .context("Failed to assemble record batch.")
+}
+
+/* COLUMN BUILDER IMPLEMENTATION */
+
+impl ColumnBuilder {
+    /// Appends `value` to this column, failing if it is of an incompatible
+    /// type. Null values are always accepted.
+    fn append(&mut self, value: ValueRef) -> Result<()> {
+        match (self, value) {
+            (ColumnBuilder::Integer(b), ValueRef::Null) => b.append_null(),
+            (ColumnBuilder::Real(b), ValueRef::Null) => b.append_null(),
+            (ColumnBuilder::Text(b), ValueRef::Null) => b.append_null(),
+            (ColumnBuilder::Blob(b), ValueRef::Null) => b.append_null(),
+            (ColumnBuilder::Integer(b), ValueRef::Integer(i)) => {
+                b.append_value(i)
+            },
+            (ColumnBuilder::Real(b), ValueRef::Real(f)) => b.append_value(f),
+            (ColumnBuilder::Real(b), ValueRef::Integer(i)) => {
+                b.append_value(i as f64)
+            },
+            (ColumnBuilder::Text(b), ValueRef::Text(t)) => {
+                b.append_value(String::from_utf8_lossy(t))
+            },
+            (ColumnBuilder::Blob(b), ValueRef::Blob(v)) => b.append_value(v),
+            (_, value) => bail!(
+                "Found a value of type '{}' that does not match the column's \
+                declared type.",
+                value.data_type(),
+            ),
+        }
+        Ok(())
+    }
+
+    /// Returns the column accumulated so far, resetting this builder.
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            ColumnBuilder::Integer(b) => Arc::new(b.finish()),
+            ColumnBuilder::Real(b) => Arc::new(b.finish()),
+            ColumnBuilder::Text(b) => Arc::new(b.finish()),
+            ColumnBuilder::Blob(b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use parquet::file::reader::FileReader;
+    use parquet::file::reader::SerializedFileReader;
+    use rusqlite::params;
+
+    use std::fs::File;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::game::zero_by;
+    use crate::solver::db::SchemaBuilder;
+    use crate::test;
+    use crate::test::DevelopmentData;
+
+    /// Used for storing the files exported in tests under their own
+    /// subdirectory.
+    const MODULE_NAME: &str = "parquet-export-tests";
+
+    #[test]
+    fn parquet_file_carries_rows_and_metadata() -> Result<()> {
+        let conn = test::database()?;
+        let game = zero_by::Session::default();
+        let schema = SchemaBuilder::new("parquet_test")
+            .players(2)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        conn.execute(
+            &schema.insert_query(),
+            params![i64::from_be_bytes(game.source()), 7, 1, -1],
+        )?;
+
+        conn.execute(
+            "ALTER TABLE parquet_test ADD COLUMN winning_moves INTEGER",
+            [],
+        )?;
+
+        let path = test::get_directory(
+            DevelopmentData::Files,
+            PathBuf::from(MODULE_NAME),
+        )?
+        .join("parquet-test.parquet");
+
+        write_rows(
+            &conn,
+            &game,
+            &schema,
+            "2-10-1-2",
+            File::create(&path)?,
+        )?;
+
+        let reader = SerializedFileReader::new(File::open(&path)?)?;
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 1);
+        assert_eq!(
+            metadata
+                .schema_descr()
+                .num_columns(),
+            5
+        );
+        assert_eq!(
+            metadata
+                .schema_descr()
+                .column(4)
+                .physical_type(),
+            parquet::basic::Type::INT64
+        );
+
+        let pairs = metadata
+            .key_value_metadata()
+            .context("Parquet file has no key-value metadata.")?;
+
+        let find = |k: &str| {
+            pairs
+                .iter()
+                .find(|kv| kv.key == k)
+                .and_then(|kv| kv.value.clone())
+        };
+
+        assert_eq!(find("nova.game").as_deref(), Some("zero-by"));
+        assert_eq!(find("nova.variant").as_deref(), Some("2-10-1-2"));
+        Ok(())
+    }
+}
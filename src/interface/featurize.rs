@@ -0,0 +1,535 @@
+//! # Featurization Module
+//!
+//! This module provides a pass over an existing solution table which computes
+//! game features (see [`crate::game::Features`]) and features derived from the
+//! solution itself (see [`crate::solver::features`]) for each of its rows,
+//! adding a column for each feature that the table does not yet have. This
+//! allows features to be engineered independently from solving a game.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use rusqlite::Connection;
+use rusqlite::params_from_iter;
+use rusqlite::types::Value;
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::game;
+use crate::game::Feature;
+use crate::game::Features;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::State;
+use crate::game::error::GameError;
+use crate::solver::db;
+use crate::solver::db::Schema;
+use crate::solver::features;
+use crate::solver::features::SolutionFeature;
+use crate::solver::features::Table;
+
+/* CONSTANTS */
+
+/// Maximum number of rows whose features are computed before being written
+/// back to the solution table in a single transaction.
+const BATCH_ROWS: usize = 1 << 16;
+
+/* DEFINITIONS */
+
+/// A feature selected to be computed over a solution table.
+enum Selected<G, const B: usize> {
+    Game(Feature<G, B>),
+    Solution(SolutionFeature<G, B>),
+}
+
+/* API */
+
+/// Computes the features of `game` named in `names` (or all of its features
+/// if `names` is empty) for the rows in the solution table described by
+/// `schema`, using up to `threads` threads for game features. Only rows
+/// missing a value for a selected feature are computed, unless `overwrite` is
+/// true.
+///
+/// # Errors
+///
+/// Fails if any of `names` is not a feature of `game`, if the game database
+/// is not available, or if there is no solution table for the game variant.
+pub fn columns<const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    names: &[String],
+    overwrite: bool,
+    threads: NonZeroUsize,
+) -> Result<()>
+where
+    G: Information + Features<B> + Implicit<B> + Sync,
+{
+    let features = select::<B, G>(names)?;
+    if features.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    fill(
+        &mut conn, game, schema, &features, overwrite, threads,
+    )
+}
+
+/// Computes the game features of `game` named in `names` (or all of them if
+/// `names` is empty) for every row of the table described by `schema` through
+/// `conn`, using up to `threads` threads. Unlike [`columns`], this does not
+/// require the table to hold solutions, so features derived from solutions
+/// are not available.
+///
+/// # Errors
+///
+/// Fails if any of `names` is not a game feature of `game`, or if the table
+/// does not exist.
+pub fn unsolved<const B: usize, G>(
+    conn: &mut Connection,
+    game: &G,
+    schema: &Schema,
+    names: &[String],
+    threads: NonZeroUsize,
+) -> Result<()>
+where
+    G: Information + Features<B> + Implicit<B> + Sync,
+{
+    let mut features = select::<B, G>(names)?;
+    if names.is_empty() {
+        features.retain(|f| matches!(f, Selected::Game(_)));
+    } else if let Some(f) = features
+        .iter()
+        .find(|f| matches!(f, Selected::Solution(_)))
+    {
+        bail!(
+            "Feature '{}' is derived from solutions, so it cannot be computed \
+            for unsolved states.",
+            f.name(),
+        )
+    }
+
+    if features.is_empty() {
+        return Ok(());
+    }
+
+    fill(conn, game, schema, &features, true, threads)
+}
+
+/// Returns the name and SQL datatype of the column holding each feature of
+/// `G` in tables where it has been computed (see [`columns`]).
+pub fn datatypes<const B: usize, G>() -> Vec<(&'static str, &'static str)>
+where
+    G: Features<B> + Implicit<B>,
+{
+    G::features()
+        .iter()
+        .map(|f| (f.name, f.datatype))
+        .chain(
+            features::features::<B, G>()
+                .iter()
+                .map(|f| (f.name, f.datatype)),
+        )
+        .collect()
+}
+
+/* HELPERS */
+
+/// Returns the features of `G` named in `names`, in the order they are named,
+/// or all of them (game features first) if `names` is empty.
+fn select<const B: usize, G>(names: &[String]) -> Result<Vec<Selected<G, B>>>
+where
+    G: Information + Features<B> + Implicit<B>,
+{
+    let game = G::features();
+    let solution = features::features::<B, G>();
+    if names.is_empty() {
+        return Ok(game
+            .into_iter()
+            .map(Selected::Game)
+            .chain(
+                solution
+                    .into_iter()
+                    .map(Selected::Solution),
+            )
+            .collect());
+    }
+
+    let mut selected: Vec<Selected<G, B>> = Vec::with_capacity(names.len());
+    for name in names {
+        if selected
+            .iter()
+            .any(|f| f.name() == name)
+        {
+            bail!("Feature '{name}' was selected more than once.")
+        }
+
+        if let Some(&f) = game
+            .iter()
+            .find(|f| f.name == name)
+        {
+            selected.push(Selected::Game(f));
+        } else if let Some(&f) = solution
+            .iter()
+            .find(|f| f.name == name)
+        {
+            selected.push(Selected::Solution(f));
+        } else {
+            bail!(GameError::FeatureUnknown {
+                game: G::info().name,
+                feature: name.clone(),
+                available: game
+                    .iter()
+                    .map(|f| f.name)
+                    .chain(solution.iter().map(|f| f.name))
+                    .collect(),
+            })
+        }
+    }
+
+    Ok(selected)
+}
+
+/// Adds a column for each of `features` missing from the table in `schema`,
+/// and writes the value of each feature for the rows of the table which need
+/// it through `conn`.
+fn fill<const B: usize, G>(
+    conn: &mut Connection,
+    game: &G,
+    schema: &Schema,
+    features: &[Selected<G, B>],
+    overwrite: bool,
+    threads: NonZeroUsize,
+) -> Result<()>
+where
+    G: Information + Implicit<B> + Sync,
+{
+    if !db::table_exists(conn, schema.table())? {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    let existing = db::columns(conn, schema.table())?;
+    for feature in features {
+        if existing
+            .iter()
+            .any(|(n, _)| n == feature.name())
+        {
+            continue;
+        }
+
+        conn.execute(
+            &format!(
+                "ALTER TABLE {} ADD COLUMN \"{}\" {}",
+                schema.table(),
+                feature.name(),
+                feature.datatype(),
+            ),
+            [],
+        )
+        .context(format!(
+            "Failed to add column for '{}'.",
+            feature.name()
+        ))?;
+    }
+
+    let update = format!(
+        "UPDATE {} SET {} WHERE \"{}\" = ?",
+        schema.table(),
+        features
+            .iter()
+            .map(|f| format!("\"{}\" = ?", f.name()))
+            .collect::<Vec<_>>()
+            .join(", "),
+        schema.key(),
+    );
+
+    let local: Vec<Feature<G, B>> = features
+        .iter()
+        .filter_map(|f| match f {
+            Selected::Game(f) => Some(*f),
+            Selected::Solution(_) => None,
+        })
+        .collect();
+
+    let mut after = None;
+    loop {
+        let batch = pending(
+            conn, schema, features, overwrite, after, BATCH_ROWS,
+        )?;
+
+        let Some(&last) = batch.last() else {
+            break;
+        };
+
+        after = Some(last);
+        let table = Table::new(conn, schema);
+        let mut rows = Vec::with_capacity(batch.len());
+        for (state, values) in compute(game, &local, &batch, threads) {
+            let mut values = values.into_iter();
+            let mut row = Vec::with_capacity(features.len() + 1);
+            for feature in features {
+                match feature {
+                    Selected::Game(_) => row.push(values.next().unwrap()),
+                    Selected::Solution(f) => {
+                        row.push((f.extract)(game, &table, state)?)
+                    },
+                }
+            }
+            rows.push((state, row));
+        }
+
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx
+                .prepare(&update)
+                .context("Failed to prepare feature update query.")?;
+
+            for (state, mut values) in rows {
+                values.push(db::key(&state));
+                stmt.execute(params_from_iter(values))?;
+            }
+        }
+        tx.commit()
+            .context("Failed to commit transaction.")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the states of up to `limit` rows in the table of `schema` which
+/// are missing a value for any of `features` (or of any rows if `overwrite` is
+/// true), in key order and starting after the key of `after` if provided.
+fn pending<const B: usize, G>(
+    conn: &Connection,
+    schema: &Schema,
+    features: &[Selected<G, B>],
+    overwrite: bool,
+    after: Option<State<B>>,
+    limit: usize,
+) -> Result<Vec<State<B>>> {
+    let mut filters = Vec::new();
+    let mut params = Vec::new();
+    if !overwrite {
+        filters.push(format!(
+            "({})",
+            features
+                .iter()
+                .map(|f| format!("\"{}\" IS NULL", f.name()))
+                .collect::<Vec<_>>()
+                .join(" OR ")
+        ));
+    }
+
+    if let Some(state) = after {
+        filters.push(format!("\"{}\" > ?", schema.key()));
+        params.push(db::key(&state));
+    }
+
+    let mut query = format!(
+        "SELECT \"{}\" FROM {}",
+        schema.key(),
+        schema.table()
+    );
+
+    if !filters.is_empty() {
+        query += &format!(" WHERE {}", filters.join(" AND "));
+    }
+
+    query += &format!(" ORDER BY \"{}\" LIMIT ?", schema.key());
+    params.push(Value::Integer(limit as i64));
+
+    let mut stmt = conn
+        .prepare(&query)
+        .context("Failed to prepare table scan query.")?;
+
+    let mut rows = stmt.query(params_from_iter(params))?;
+    let mut states = Vec::new();
+    while let Some(row) = rows.next()? {
+        let state = db::state(row.get_ref(0)?)
+            .context("Failed to read keys from solution table.")?;
+
+        states.push(state);
+    }
+    Ok(states)
+}
+
+/// Computes the value of each of `features` for each of `states`, splitting
+/// the work evenly across `threads` threads.
+fn compute<const B: usize, G>(
+    game: &G,
+    features: &[Feature<G, B>],
+    states: &[State<B>],
+    threads: NonZeroUsize,
+) -> Vec<(State<B>, Vec<Value>)>
+where
+    G: Sync,
+{
+    let extract = |states: &[State<B>]| {
+        states
+            .iter()
+            .map(|&state| {
+                let values = features
+                    .iter()
+                    .map(|f| (f.extract)(game, state))
+                    .collect();
+
+                (state, values)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    if threads.get() == 1 {
+        return extract(states);
+    }
+
+    let chunk = states
+        .len()
+        .div_ceil(threads.get())
+        .max(1);
+
+    thread::scope(|s| {
+        let workers: Vec<_> = states
+            .chunks(chunk)
+            .map(|c| s.spawn(move || extract(c)))
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|w| {
+                w.join()
+                    .expect("Feature extraction thread panicked.")
+            })
+            .collect()
+    })
+}
+
+/* SELECTED FEATURE IMPLEMENTATION */
+
+impl<G, const B: usize> Selected<G, B> {
+    fn name(&self) -> &'static str {
+        match self {
+            Selected::Game(f) => f.name,
+            Selected::Solution(f) => f.name,
+        }
+    }
+
+    fn datatype(&self) -> &'static str {
+        match self {
+            Selected::Game(f) => f.datatype,
+            Selected::Solution(f) => f.datatype,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rusqlite::params;
+
+    use super::*;
+    use crate::game::mnk;
+    use crate::solver::db::SchemaBuilder;
+    use crate::test;
+
+    #[test]
+    fn features_are_added_and_filled_in() -> Result<()> {
+        let mut conn = test::database()?;
+        let game = mnk::Session::default();
+        let schema = SchemaBuilder::new("featurize_test")
+            .players(2)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .column("player", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        let mut states = vec![game.source()];
+        states.extend(game.adjacent(game.source()));
+        for state in states.iter() {
+            conn.execute(
+                &schema.insert_query(),
+                params![i64::from_be_bytes(*state), 0, 0, 0, 0],
+            )?;
+        }
+
+        let features = select(&["ply".into()])?;
+        let threads = NonZeroUsize::new(3).unwrap();
+        fill(
+            &mut conn, &game, &schema, &features, false, threads,
+        )?;
+
+        let plies = conn
+            .prepare("SELECT ply FROM featurize_test")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+
+        assert_eq!(plies.len(), states.len());
+        assert_eq!(
+            plies
+                .iter()
+                .filter(|&&p| p == 0)
+                .count(),
+            1
+        );
+        assert_eq!(
+            plies
+                .iter()
+                .filter(|&&p| p == 1)
+                .count(),
+            9
+        );
+        assert!(
+            pending(&conn, &schema, &features, false, None, BATCH_ROWS)?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pending_states_are_paged_in_key_order() -> Result<()> {
+        let conn = test::database()?;
+        let game = mnk::Session::default();
+        let schema = SchemaBuilder::new("featurize_paging_test")
+            .players(2)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .column("player", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        let mut states = vec![game.source()];
+        states.extend(game.adjacent(game.source()));
+        for state in states.iter() {
+            conn.execute(
+                &schema.insert_query(),
+                params![i64::from_be_bytes(*state), 0, 0, 0, 0],
+            )?;
+        }
+
+        let features = select::<8, mnk::Session>(&["ply".into()])?;
+        let mut paged = Vec::new();
+        let mut after = None;
+        loop {
+            let page = pending(&conn, &schema, &features, true, after, 3)?;
+            let Some(&last) = page.last() else {
+                break;
+            };
+
+            assert!(page.len() <= 3);
+            after = Some(last);
+            paged.extend(page);
+        }
+
+        states.sort_by_key(|s| i64::from_be_bytes(*s));
+        assert_eq!(paged, states);
+        Ok(())
+    }
+}
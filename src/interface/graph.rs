@@ -0,0 +1,235 @@
+//! # Graph Rendering Module
+//!
+//! This module renders the graph of states reachable from the starting state
+//! of a game variant in the DOT language, so that it can be drawn with tools
+//! like Graphviz. Nodes are labeled by their state encodings and, if the game
+//! variant has been solved, colored by their solved values, where green, yellow
+//! and red mean winning, tying, and losing for the player in turn, and where
+//! paler colors mean higher remoteness.
+
+use anyhow::Context;
+use anyhow::Result;
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use crate::game;
+use crate::game::Codec;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::State;
+use crate::solver::Remoteness;
+use crate::solver::db;
+use crate::solver::db::Schema;
+use crate::solver::features::Outcome;
+use crate::solver::features::Table;
+
+/* CONSTANTS */
+
+/// Hues of the fill colors of winning, tying, and losing states, in the HSV
+/// color space used by Graphviz.
+const HUES: [f64; 3] = [0.333, 0.15, 0.0];
+
+/* DEFINITIONS */
+
+/// A state rendered as a node of the graph.
+struct Vertex {
+    /// Encoding of the state.
+    label: String,
+
+    /// Solved attributes of the state, if available.
+    outcome: Option<Outcome>,
+
+    /// Whether the state has no outgoing edges in the game.
+    terminal: bool,
+
+    /// Whether the state has children which were not rendered.
+    truncated: bool,
+}
+
+/* API */
+
+/// Prints the graph of states of `game` reachable from its starting state in
+/// at most `depth` moves (or all of them, if not provided) in DOT format to
+/// STDOUT. States are colored according to the solution table in `schema` if
+/// it exists, and left uncolored otherwise.
+///
+/// # Errors
+///
+/// Fails if the game database is not available, or if a state cannot be
+/// encoded.
+pub fn dot<const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    depth: Option<usize>,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B>,
+{
+    let conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    let table = if db::table_exists(&conn, schema.table())? {
+        Some(Table::new(&conn, schema))
+    } else {
+        None
+    };
+
+    print!("{}", render(game, table.as_ref(), depth)?);
+    Ok(())
+}
+
+/* HELPERS */
+
+/// Returns the DOT representation of the graph of states of `game` reachable
+/// in at most `depth` moves, colored according to `table` if provided.
+fn render<const B: usize, G>(
+    game: &G,
+    table: Option<&Table>,
+    depth: Option<usize>,
+) -> Result<String>
+where
+    G: Information + Codec<B> + Implicit<B>,
+{
+    let mut ids: HashMap<State<B>, usize> = HashMap::new();
+    let mut vertices = Vec::new();
+    let mut edges = Vec::new();
+    let mut queue = VecDeque::new();
+
+    let source = game.source();
+    ids.insert(source, 0);
+    queue.push_back((source, 0));
+    while let Some((state, distance)) = queue.pop_front() {
+        let terminal = game.sink(state);
+        let expand = !terminal && depth.is_none_or(|d| distance < d);
+        if expand {
+            for child in game.adjacent(state) {
+                let next = ids.len();
+                let id = *ids
+                    .entry(child)
+                    .or_insert_with(|| {
+                        queue.push_back((child, distance + 1));
+                        next
+                    });
+
+                edges.push((ids[&state], id));
+            }
+        }
+
+        let outcome =
+            if let Some(table) = table { table.lookup(state)? } else { None };
+
+        vertices.push(Vertex {
+            label: game.encode(state)?,
+            outcome,
+            terminal,
+            truncated: !terminal && !expand,
+        });
+    }
+
+    let max = vertices
+        .iter()
+        .filter_map(|v| v.outcome.as_ref())
+        .map(|o| o.remoteness)
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    writeln!(out, "digraph \"{}\" {{", escape(G::info().name))?;
+    writeln!(out, "    node [style=filled, fillcolor=white];")?;
+    for (id, vertex) in vertices.iter().enumerate() {
+        writeln!(
+            out,
+            "    n{id} [{}];",
+            attributes(vertex, id == 0, max)
+        )?;
+    }
+
+    for (from, to) in edges {
+        writeln!(out, "    n{from} -> n{to};")?;
+    }
+
+    writeln!(out, "}}")?;
+    Ok(out)
+}
+
+/// Returns the DOT attribute list of `vertex`, given whether it is the source
+/// of the graph and the `max` remoteness among all rendered vertices.
+fn attributes(vertex: &Vertex, source: bool, max: Remoteness) -> String {
+    let mut label = escape(&vertex.label);
+    let mut attrs = Vec::new();
+    if let Some(outcome) = &vertex.outcome {
+        let value = outcome.utility[outcome.player];
+        let hue = match value.signum() {
+            1 => HUES[0],
+            0 => HUES[1],
+            _ => HUES[2],
+        };
+
+        let saturation =
+            0.9 - 0.6 * (outcome.remoteness as f64 / max.max(1) as f64);
+
+        label += &format!("\\nr={}", outcome.remoteness);
+        attrs.push(format!(
+            "fillcolor=\"{hue:.3} {saturation:.3} 0.95\""
+        ));
+    }
+
+    attrs.insert(0, format!("label=\"{label}\""));
+    if vertex.terminal {
+        attrs.push("shape=box".into());
+    }
+
+    if vertex.truncated {
+        attrs.push("style=\"filled,dashed\"".into());
+    }
+
+    if source {
+        attrs.push("penwidth=2.5".into());
+    }
+
+    attrs.join(", ")
+}
+
+/// Returns `s` escaped for use within a double-quoted DOT string.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::Variable;
+    use crate::game::zero_by;
+    use crate::test;
+
+    #[test]
+    fn renders_reachable_states_up_to_depth() -> Result<()> {
+        let game = zero_by::Session::variant("2-3-1-2".into())?;
+        let full = render(&game, None, None)?;
+        assert_eq!(full.matches(" -> ").count(), 6);
+        assert_eq!(full.matches("label=").count(), 6);
+        assert!(full.contains("label=\"3-0\", penwidth=2.5"));
+        assert!(!full.contains("fillcolor=\""));
+
+        let partial = render(&game, None, Some(1))?;
+        assert_eq!(partial.matches(" -> ").count(), 2);
+        assert_eq!(partial.matches("dashed").count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn solved_states_are_colored_by_value() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("graph_test")?;
+        let table = Table::new(&conn, &schema);
+        let out = render(&game, Some(&table), None)?;
+        assert!(out.contains("label=\"3-0\\nr=2\", fillcolor=\"0.333 0.300"));
+        assert!(out.contains("label=\"1-1\\nr=1\", fillcolor=\"0.000 0.600"));
+        assert!(out.contains("label=\"0-0\\nr=0\", fillcolor=\"0.333 0.900"));
+        Ok(())
+    }
+}
@@ -13,7 +13,21 @@ mod util;
 
 /* INTERFACE IMPLEMENTATIONS */
 
+pub mod benchmark;
 pub mod cli;
+pub mod config;
+pub mod diff;
+pub mod export;
+pub mod featurize;
+pub mod graph;
+pub mod play;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod query;
+pub mod sample;
+pub mod serve;
+pub mod session;
+pub mod verify;
 
 /* DEFINITIONS */
 
@@ -28,6 +42,57 @@ pub enum InfoFormat {
     Json,
 }
 
+/// Describes the format in which calls to the `export` CLI command should
+/// serialize the rows of a solution table, which should be machine-readable.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum ExportFormat {
+    /// Comma-separated values, with a header row of column names.
+    Csv,
+
+    /// One JSON object per line, keyed by column names.
+    Jsonl,
+
+    /// Apache Parquet file, with columns typed according to the table schema.
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+/// Describes how moves are chosen during the playouts used by the `sample` CLI
+/// command to visit game states.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SamplePolicy {
+    /// Every available move is equally likely to be chosen.
+    Uniform,
+
+    /// Moves are chosen with probability increasing in their utility for the
+    /// player making them, as estimated through random rollouts.
+    Weighted,
+}
+
+/// Describes how the `sample` CLI command estimates the utility of sampled
+/// states which are not terminal.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Estimator {
+    /// Average utility of random playouts from the state.
+    Rollout,
+
+    /// Average utility of the playouts of a Monte Carlo tree search rooted at
+    /// the state.
+    Mcts,
+}
+
+/// Describes who controls the players that are not controlled by humans in
+/// games started through the `play` CLI command.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Opponent {
+    /// Plays perfectly according to the game variant's solution.
+    Solution,
+
+    /// Plays the moves found by a Monte Carlo tree search, which does not
+    /// require the game variant to be solved.
+    Mcts,
+}
+
 /// Specifies a category of information kept about a game. Used for finding
 /// specific information about game implementations through the `info` CLI
 /// command. See [`crate::game::GameData`] for the provider data structure.
@@ -82,18 +147,18 @@ pub enum GameAttribute {
 /// this compatibility to eschew unnecessary work by considering the following
 /// scenarios:
 ///
-/// 1. If an existing database file exists, is not corrupted, and sufficient, it
-/// will be used to serve a request. For example, if there is an existing strong
-/// solution on a game and a command is issued to compute a weak solution for
-/// it, then nothing should be done.
+/// 1. If an existing database file exists, is not corrupted, and sufficient,
+///    it will be used to serve a request. For example, if there is an existing
+///    strong solution on a game and a command is issued to compute a weak
+///    solution for it, then nothing should be done.
 ///
 /// 2. If an insufficient database file exists and is not corrupted, the
-/// existing information about the solution to the underlying game should be
-/// used to produce the remainder of the request.
+///    existing information about the solution to the underlying game should
+///    be used to produce the remainder of the request.
 ///
 /// 3. Finally, if a database file does not exist or is corrupted (beyond any
-/// possibility of repair by a database recovery mechanism), then it will be
-/// computed again up to the number of states associated with the request.
+///    possibility of repair by a database recovery mechanism), then it will be
+///    computed again up to the number of states associated with the request.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum IOMode {
     /// Use existing resources and compute whatever is missing.
@@ -126,3 +191,41 @@ impl fmt::Display for InfoFormat {
         }
     }
 }
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "csv"),
+            ExportFormat::Jsonl => write!(f, "jsonl"),
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => write!(f, "parquet"),
+        }
+    }
+}
+
+impl fmt::Display for SamplePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SamplePolicy::Uniform => write!(f, "uniform"),
+            SamplePolicy::Weighted => write!(f, "weighted"),
+        }
+    }
+}
+
+impl fmt::Display for Opponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Opponent::Solution => write!(f, "solution"),
+            Opponent::Mcts => write!(f, "mcts"),
+        }
+    }
+}
+
+impl fmt::Display for Estimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Estimator::Rollout => write!(f, "rollout"),
+            Estimator::Mcts => write!(f, "mcts"),
+        }
+    }
+}
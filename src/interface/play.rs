@@ -0,0 +1,336 @@
+//! # Interactive Play Module
+//!
+//! This module provides a terminal loop where people can play a game against
+//! a solution stored in the game database. Human players choose their moves by
+//! number or by typing the encoding of the state they wish to move to, and all
+//! other players respond with the best move according to the solution, or with
+//! the move chosen by a Monte Carlo tree search when playing unsolved games.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use fastrand::Rng;
+use rusqlite::Connection;
+
+use std::io::BufRead;
+use std::io::Write;
+
+use crate::game;
+use crate::game::Codec;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::Player;
+use crate::game::PlayerCount;
+use crate::game::State;
+use crate::interface::Opponent;
+use crate::solver::Game;
+use crate::solver::IntegerUtility;
+use crate::solver::algorithm::mcts;
+use crate::solver::db;
+use crate::solver::db::Schema;
+use crate::solver::features::Table;
+
+/* CONSTANTS */
+
+/// Explanation of the commands available at the prompt.
+const HELP: &str = "Enter the number of a move, the encoding of the state to \
+move to, 'undo' to take back your last move, or 'quit' to leave the game.";
+
+/* DEFINITIONS */
+
+/// A command entered by a human player at the prompt.
+enum Command<const B: usize> {
+    Move(State<B>),
+    Undo,
+    Quit,
+    Help,
+}
+
+/// Chooses the moves of the players who are not controlled by humans.
+enum Agent<'a> {
+    /// Plays the best move according to a solution table.
+    Solution {
+        conn: Connection,
+        schema: &'a Schema,
+    },
+
+    /// Plays the move chosen by a Monte Carlo tree search.
+    Search { config: mcts::Config, rng: Rng },
+}
+
+/* API */
+
+/// Starts an interactive game of `game` on the terminal, where the players in
+/// `humans` are prompted for their moves and all others are played by the
+/// `opponent`. The solution opponent plays according to the solution table
+/// described by `schema`, and the search opponent plays the moves chosen by
+/// searches configured by `search`. If `humans` is empty, the player who moves
+/// first at the starting state is the only human player.
+///
+/// # Errors
+///
+/// Fails if playing against the solution and either the game database is not
+/// available or there is no solution table for the game variant.
+pub fn interactive<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    humans: Vec<Player>,
+    opponent: Opponent,
+    search: mcts::Config,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    let agent = match opponent {
+        Opponent::Solution => {
+            let conn = game::util::database()
+                .context("Failed to obtain connection to game database.")?;
+
+            Agent::Solution { conn, schema }
+        },
+        Opponent::Mcts => Agent::Search {
+            config: search,
+            rng: Rng::new(),
+        },
+    };
+
+    let stdin = std::io::stdin().lock();
+    let stdout = std::io::stdout().lock();
+    play::<N, B, _, _, _>(agent, game, humans, stdin, stdout)
+}
+
+/* HELPERS */
+
+/// Runs a game of `game` against `agent`, reading human input from `input`
+/// and writing the transcript of the game to `output`.
+fn play<const N: PlayerCount, const B: usize, G, R, W>(
+    mut agent: Agent,
+    game: &G,
+    mut humans: Vec<Player>,
+    mut input: R,
+    mut output: W,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+    R: BufRead,
+    W: Write,
+{
+    if let Agent::Solution { conn, schema } = &agent
+        && !db::table_exists(conn, schema.table())?
+    {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    let source = game.source();
+    if humans.is_empty() {
+        humans.push(game.turn(source));
+    }
+
+    let mut history = vec![source];
+    while let Some(&state) = history.last() {
+        let player = game.turn(state);
+        writeln!(output, "\n{}", game.encode(state)?)?;
+        if game.sink(state) {
+            writeln!(
+                output,
+                "Game over. Utilities: {:?}",
+                game.utility(state)
+            )?;
+            break;
+        }
+
+        let children = game.adjacent(state);
+        if !humans.contains(&player) {
+            let next = agent.respond::<N, B, G>(game, state, &children)?;
+            writeln!(
+                output,
+                "Player {player} moves to {}",
+                game.encode(next)?
+            )?;
+            history.push(next);
+            continue;
+        }
+
+        writeln!(output, "Player {player}, choose your move:")?;
+        for (i, &child) in children.iter().enumerate() {
+            writeln!(output, "  {}. {}", i + 1, game.encode(child)?)?;
+        }
+
+        write!(output, "> ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        match parse(game, state, &children, line.trim()) {
+            Ok(Command::Move(next)) => history.push(next),
+            Ok(Command::Quit) => break,
+            Ok(Command::Help) => writeln!(output, "{HELP}")?,
+            Ok(Command::Undo) => {
+                if !undo::<N, B, G>(game, &humans, &mut history) {
+                    writeln!(output, "There are no moves to undo.")?;
+                }
+            },
+            Err(e) => writeln!(output, "{e:#}")?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Interprets the `line` entered by a human player at `state`, whose possible
+/// transitions are `children`.
+fn parse<const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    children: &[State<B>],
+    line: &str,
+) -> Result<Command<B>>
+where
+    G: Information + Codec<B> + Implicit<B>,
+{
+    match line {
+        "undo" | "u" => Ok(Command::Undo),
+        "quit" | "q" | "exit" => Ok(Command::Quit),
+        "help" | "h" | "?" => Ok(Command::Help),
+        _ => {
+            if let Ok(i) = line.parse::<usize>() {
+                if i == 0 || i > children.len() {
+                    bail!("There is no move numbered {i}.")
+                }
+                Ok(Command::Move(children[i - 1]))
+            } else {
+                let next = game
+                    .decode(line.to_owned())
+                    .context("Failed to parse move.")?;
+
+                game::util::verify_transition(game, state, next)?;
+                Ok(Command::Move(next))
+            }
+        },
+    }
+}
+
+/// Reverts `history` to the last state before the most recent move made by
+/// any of the `humans`, returning false if there is no such move to undo.
+fn undo<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    humans: &[Player],
+    history: &mut Vec<State<B>>,
+) -> bool
+where
+    G: Game<N, B>,
+{
+    let mut undone = history.clone();
+    while undone.len() > 1 {
+        undone.pop();
+        let last = *undone.last().unwrap();
+        if humans.contains(&game.turn(last)) {
+            *history = undone;
+            return true;
+        }
+    }
+    false
+}
+
+/* AGENT IMPLEMENTATION */
+
+impl Agent<'_> {
+    /// Returns the move this agent makes at `state`, whose possible transitions
+    /// in `game` are `children`.
+    fn respond<const N: PlayerCount, const B: usize, G>(
+        &mut self,
+        game: &G,
+        state: State<B>,
+        children: &[State<B>],
+    ) -> Result<State<B>>
+    where
+        G: Codec<B> + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+    {
+        match self {
+            Agent::Solution { conn, schema } => Table::new(conn, schema)
+                .best(game.turn(state), children)?
+                .map(|(child, _)| child)
+                .context("Attempted to choose a move at a terminal state."),
+            Agent::Search { config, rng } => {
+                mcts::choose::<N, B, G>(game, state, config, rng)
+                    .context("Attempted to choose a move at a terminal state.")
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::Variable;
+    use crate::game::zero_by;
+    use crate::test;
+
+    #[test]
+    fn computer_plays_best_moves() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("play_test")?;
+        let mut out = Vec::new();
+        play::<2, 8, _, _, _>(
+            Agent::Solution {
+                conn,
+                schema: &schema,
+            },
+            &game,
+            vec![1],
+            "".as_bytes(),
+            &mut out,
+        )?;
+
+        let out = String::from_utf8(out)?;
+        assert!(out.contains("Player 0 moves to 1-1"));
+        assert!(out.contains("Player 1, choose your move:"));
+        Ok(())
+    }
+
+    #[test]
+    fn humans_can_move_and_undo() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("play_test")?;
+        let input = "5\n2-1\nundo\n1-1\n";
+        let mut out = Vec::new();
+        play::<2, 8, _, _, _>(
+            Agent::Solution {
+                conn,
+                schema: &schema,
+            },
+            &game,
+            vec![0],
+            input.as_bytes(),
+            &mut out,
+        )?;
+
+        let out = String::from_utf8(out)?;
+        assert!(out.contains("There is no move numbered 5."));
+        assert!(out.contains("Player 1 moves to 1-0"));
+        assert!(out.contains("Player 1 moves to 0-0"));
+        assert!(out.contains("Game over. Utilities: [1, -1]"));
+        Ok(())
+    }
+
+    #[test]
+    fn search_opponent_plays_without_solution() -> Result<()> {
+        let game = zero_by::Session::variant("2-3-1-2".into())?;
+        let agent = Agent::Search {
+            config: mcts::Config::default(),
+            rng: Rng::with_seed(11),
+        };
+
+        let mut out = Vec::new();
+        play::<2, 8, _, _, _>(agent, &game, vec![1], "".as_bytes(), &mut out)?;
+        let out = String::from_utf8(out)?;
+        assert!(out.contains("Player 0 moves to 1-1"));
+        Ok(())
+    }
+}
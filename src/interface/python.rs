@@ -0,0 +1,201 @@
+//! # Python Bindings Module
+//!
+//! This module exposes game sessions to Python through a native extension
+//! module named `gamesman_nova`, which can be built with
+//! [maturin](https://www.maturin.rs/) by running `maturin develop` at the root
+//! of this repository. For example:
+//!
+//! ```python
+//! import gamesman_nova as nova
+//!
+//! game = nova.Session("zero-by", "2-10-1-2")
+//! game.solve()
+//!
+//! state = game.source()
+//! for child in game.adjacent(state):
+//!     print(game.encode(child), game.lookup(child)["remoteness"])
+//! ```
+//!
+//! States are exchanged as their values in the `state` column of solution
+//! tables, so that they can be matched against exported datasets. These are
+//! integers for most game variants, and `bytes` objects for those whose states
+//! take more than 8 bytes to encode (see [`key`]). Solutions are stored in the
+//! same database as they are through the command line, which can be configured
+//! as described in [`config`], except for the `--db` option.
+//!
+//! [`config`]: crate::interface::config
+//! [`key`]: crate::solver::db::key
+
+use clap::ValueEnum;
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use std::num::NonZeroUsize;
+
+use crate::game::GameModule;
+use crate::game::registry;
+use crate::interface::IOMode;
+use crate::interface::InfoFormat;
+use crate::interface::session::DynSession;
+
+/* DEFINITIONS */
+
+/// A session of a game variant, through which its states can be explored and
+/// its solution computed and looked up.
+#[pyclass(name = "Session", unsendable)]
+pub struct Session {
+    inner: Box<dyn DynSession>,
+}
+
+/// A state as exchanged with Python, which is an integer when it fits in one.
+#[derive(FromPyObject)]
+enum Key {
+    Integer(i64),
+    Bytes(Vec<u8>),
+}
+
+/* MODULE */
+
+/// Registers the classes and functions of the `gamesman_nova` module.
+#[pymodule]
+fn gamesman_nova(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Session>()?;
+    m.add_function(wrap_pyfunction!(games, m)?)?;
+    Ok(())
+}
+
+/// Returns the names of all available games.
+#[pyfunction]
+fn games() -> Vec<&'static str> {
+    registry::GAMES
+        .iter()
+        .map(|e| (e.info)().name)
+        .collect()
+}
+
+/* SESSION IMPLEMENTATION */
+
+#[pymethods]
+impl Session {
+    /// Initializes a session of the game named `game` under `variant`, or
+    /// under the game's default variant if none is provided.
+    #[new]
+    #[pyo3(signature = (game, variant = None))]
+    fn new(game: &str, variant: Option<String>) -> PyResult<Self> {
+        let module = GameModule::from_str(game, false).map_err(|_| {
+            PyValueError::new_err(format!("There is no game '{game}'."))
+        })?;
+
+        let inner = (registry::entry(module).session)(variant)?;
+        Ok(Session { inner })
+    }
+
+    /// Name of the game this session is of.
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.inner.data().name
+    }
+
+    /// Returns the starting state of this game variant.
+    fn source<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        key(py, self.inner.start())
+    }
+
+    /// Returns true if `state` is terminal.
+    fn sink(&self, state: Key) -> PyResult<bool> {
+        Ok(self
+            .inner
+            .terminal(&bytes(state))?)
+    }
+
+    /// Returns the states reachable from `state` in one move.
+    fn adjacent<'py>(
+        &self,
+        py: Python<'py>,
+        state: Key,
+    ) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        self.inner
+            .children(&bytes(state))?
+            .into_iter()
+            .map(|s| key(py, s))
+            .collect()
+    }
+
+    /// Returns the string encoding of `state`.
+    fn encode(&self, state: Key) -> PyResult<String> {
+        Ok(self.inner.show(&bytes(state))?)
+    }
+
+    /// Returns the state encoded by the string `encoding`.
+    fn decode<'py>(
+        &self,
+        py: Python<'py>,
+        encoding: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        key(py, self.inner.read(encoding)?)
+    }
+
+    /// Advances the starting state of this session to the last state encoded
+    /// in `history`, which must be a valid sequence of moves from the current
+    /// starting state.
+    fn forward(&mut self, history: Vec<String>) -> PyResult<()> {
+        Ok(self.inner.advance(history)?)
+    }
+
+    /// Computes the solution to this game variant along with all of its
+    /// features and stores them in the game database, where `mode` is one of
+    /// the modes of `nova build`.
+    #[pyo3(signature = (mode = "constructive"))]
+    fn solve(&mut self, mode: &str) -> PyResult<()> {
+        let mode = IOMode::from_str(mode, true).map_err(|_| {
+            PyValueError::new_err(format!("There is no mode '{mode}'."))
+        })?;
+
+        self.inner.solve(mode)?;
+        if mode != IOMode::Forgetful {
+            self.inner
+                .featurize(Vec::new(), false, NonZeroUsize::MIN)?
+        }
+        Ok(())
+    }
+
+    /// Returns a dictionary with the solved attributes of `state`, including
+    /// those of its children under the key `children` if `children` is true.
+    #[pyo3(signature = (state, children = false))]
+    fn lookup<'py>(
+        &self,
+        py: Python<'py>,
+        state: Key,
+        children: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let encoding = self.inner.show(&bytes(state))?;
+
+        let out = self
+            .inner
+            .query(encoding, children, InfoFormat::Json)?;
+
+        py.import("json")?
+            .call_method1("loads", (out,))
+    }
+}
+
+/* HELPERS */
+
+/// Returns the value under which the state with bytes `state` is stored in
+/// solution tables, as a Python object.
+fn key(py: Python<'_>, state: Vec<u8>) -> PyResult<Bound<'_, PyAny>> {
+    match <[u8; 8]>::try_from(state.as_slice()) {
+        Ok(bytes) => i64::from_be_bytes(bytes).into_bound_py_any(py),
+        Err(_) => Ok(PyBytes::new(py, &state).into_any()),
+    }
+}
+
+/// Returns the bytes of the state that is stored under `key`.
+fn bytes(key: Key) -> Vec<u8> {
+    match key {
+        Key::Integer(i) => i.to_be_bytes().to_vec(),
+        Key::Bytes(b) => b,
+    }
+}
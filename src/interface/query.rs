@@ -0,0 +1,375 @@
+//! # Position Query Module
+//!
+//! This module provides routines for looking up the solved attributes of
+//! individual game states (and optionally those of their children) in the
+//! game database, formatting them for human or machine consumption.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use fastrand::Rng;
+use rusqlite::Connection;
+use rusqlite::types::ValueRef;
+use serde_json::Map;
+
+use crate::game;
+use crate::game::Codec;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::Player;
+use crate::game::State;
+use crate::interface::InfoFormat;
+use crate::interface::util::json_value;
+use crate::interface::util::legible_value;
+use crate::solver::db;
+use crate::solver::db::Record;
+use crate::solver::db::Schema;
+use crate::solver::features::Outcome;
+use crate::solver::features::Table;
+
+/* DEFINITIONS */
+
+/// The solved attributes of a queried state, along with those of its children
+/// if they were requested.
+struct Position {
+    encoding: String,
+    record: Record,
+    children: Option<Vec<Child>>,
+}
+
+/// The solved attributes of a state reachable in one move from a queried one,
+/// or `None` if it is not present in the solution table.
+struct Child {
+    encoding: String,
+    record: Option<Record>,
+    optimal: bool,
+}
+
+/* API */
+
+/// Returns the solved attributes of the state encoded by `state` according to
+/// the solution table described by `schema`, including those of its children
+/// if `children` is true, rendered in the specified `format`.
+///
+/// # Errors
+///
+/// Fails if `state` is not a valid encoding under `game`, if the game database
+/// is not available, or if the state is not present in the solution table.
+pub fn position<const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    state: String,
+    children: bool,
+    format: InfoFormat,
+) -> Result<String>
+where
+    G: Information + Codec<B> + Implicit<B>,
+{
+    let conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    let state = game
+        .decode(state)
+        .context("Failed to decode queried state.")?;
+
+    let position = lookup(&conn, game, schema, state, children)?;
+    render(&position, format)
+}
+
+/// Returns the solved attributes of up to `count` distinct states chosen at
+/// random (using `seed`, if provided) from the solution table described by
+/// `schema`, each rendered in the specified `format`. All of them are read
+/// through a single connection to the game database.
+///
+/// # Errors
+///
+/// Fails if the game database is not available, or if the game variant has not
+/// been solved.
+pub fn random<const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    count: usize,
+    seed: Option<u64>,
+    format: InfoFormat,
+) -> Result<Vec<String>>
+where
+    G: Information + Codec<B> + Implicit<B>,
+{
+    let conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    let mut rng =
+        if let Some(seed) = seed { Rng::with_seed(seed) } else { Rng::new() };
+
+    choose::<B, G>(&conn, schema, count, &mut rng)?
+        .into_iter()
+        .map(|s| render(&lookup(&conn, game, schema, s, false)?, format))
+        .collect()
+}
+
+/* HELPERS */
+
+/// Fetches the solved attributes of `state` (and of its children, if
+/// `children` is true) from the table described by `schema` through `conn`.
+fn lookup<const B: usize, G>(
+    conn: &Connection,
+    game: &G,
+    schema: &Schema,
+    state: State<B>,
+    children: bool,
+) -> Result<Position>
+where
+    G: Information + Codec<B> + Implicit<B>,
+{
+    if !db::table_exists(conn, schema.table())? {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    let encoding = game.encode(state)?;
+    let parent = if let Some(r) = fetch(conn, schema, state)? {
+        r
+    } else {
+        bail!(
+            "The state '{encoding}' is not present in the solution table. It \
+            may be unreachable from the variant's starting state.",
+        )
+    };
+
+    let children = if children {
+        let table = Table::new(conn, schema);
+        let turn = table.outcome(state)?.player;
+        let adjacent = game.adjacent(state);
+        let best = best(&table, turn, &adjacent)?;
+        let mut out = Vec::new();
+        for child in adjacent {
+            let record = fetch(conn, schema, child)?;
+            let optimal = if let Some(best) = &best {
+                table
+                    .lookup(child)?
+                    .is_some_and(|o| {
+                        o.utility[turn] == best.utility[turn]
+                            && o.remoteness == best.remoteness
+                    })
+            } else {
+                false
+            };
+
+            out.push(Child {
+                encoding: game.encode(child)?,
+                record,
+                optimal,
+            });
+        }
+        Some(out)
+    } else {
+        None
+    };
+
+    Ok(Position {
+        encoding,
+        record: parent,
+        children,
+    })
+}
+
+/// Returns up to `count` distinct states chosen at random from the table
+/// described by `schema` through `conn`.
+fn choose<const B: usize, G>(
+    conn: &Connection,
+    schema: &Schema,
+    count: usize,
+    rng: &mut Rng,
+) -> Result<Vec<State<B>>>
+where
+    G: Information,
+{
+    if !db::table_exists(conn, schema.table())? {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    db::sample(conn, schema, None, count, rng)
+        .context("Failed to sample keys from solution table.")
+}
+
+/// Retrieves the record of `state` in the table described by `schema` through
+/// `conn`, without its primary key column.
+fn fetch<const B: usize>(
+    conn: &Connection,
+    schema: &Schema,
+    state: State<B>,
+) -> Result<Option<Record>> {
+    let mut record = db::record(conn, schema, &state)?;
+    if let Some(r) = record.as_mut() {
+        r.retain(|(n, _)| n != schema.key());
+    }
+    Ok(record)
+}
+
+/// Returns the solved attributes of the child in `children` that the player
+/// `turn` moves to under perfect play (see [`Table::best`]), or `None` if any
+/// of them is not present in `table`, in which case no child is optimal.
+fn best<const B: usize>(
+    table: &Table,
+    turn: Player,
+    children: &[State<B>],
+) -> Result<Option<Outcome>> {
+    for &child in children {
+        if table.lookup(child)?.is_none() {
+            return Ok(None);
+        }
+    }
+
+    Ok(table
+        .best(turn, children)?
+        .map(|(_, o)| o))
+}
+
+/// Returns the string representation of `position` in the given `format`.
+fn render(position: &Position, format: InfoFormat) -> Result<String> {
+    match format {
+        InfoFormat::Legible => {
+            let mut out = format!("{}\n", position.encoding);
+            for (name, value) in position.record.iter() {
+                out += &format!("\t{name}: {}\n", legible_value(value.into()));
+            }
+
+            if let Some(children) = &position.children {
+                out += &format!("\nchildren ({}):\n", children.len());
+                for child in children {
+                    let marker = if child.optimal { "*" } else { " " };
+                    out += &format!("{marker} {}\n", child.encoding);
+                    if let Some(record) = &child.record {
+                        for (name, value) in record.iter() {
+                            out += &format!(
+                                "\t{name}: {}\n",
+                                legible_value(value.into())
+                            );
+                        }
+                    } else {
+                        out += "\t(not in solution table)\n";
+                    }
+                }
+            }
+            Ok(out)
+        },
+        InfoFormat::Json => {
+            let mut map = json_record(&position.encoding, &position.record);
+            if let Some(children) = &position.children {
+                let children = children
+                    .iter()
+                    .map(|c| {
+                        let mut map = if let Some(record) = &c.record {
+                            json_record(&c.encoding, record)
+                        } else {
+                            let mut map = Map::new();
+                            map.insert(
+                                "state".into(),
+                                c.encoding.clone().into(),
+                            );
+                            map
+                        };
+                        map.insert("optimal".into(), c.optimal.into());
+                        serde_json::Value::Object(map)
+                    })
+                    .collect();
+
+                map.insert(
+                    "children".into(),
+                    serde_json::Value::Array(children),
+                );
+            }
+
+            let mut out = serde_json::to_string(&map)
+                .context("Failed to generate JSON object from position.")?;
+
+            out.push('\n');
+            Ok(out)
+        },
+    }
+}
+
+/// Returns a JSON object containing the columns in `record`, along with the
+/// state's `encoding`.
+fn json_record(
+    encoding: &str,
+    record: &Record,
+) -> Map<String, serde_json::Value> {
+    let mut map = Map::new();
+    for (name, value) in record.iter() {
+        map.insert(name.clone(), json_value(ValueRef::from(value)));
+    }
+    map.insert("state".into(), encoding.into());
+    map
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::zero_by;
+    use crate::solver::db::SchemaBuilder;
+    use crate::test;
+
+    #[test]
+    fn optimal_children_preserve_value_and_remoteness() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("query_optimal_test")?;
+        let state = game.decode("3-0".into())?;
+        let position = lookup(&conn, &game, &schema, state, true)?;
+        let optimal: Vec<_> = position
+            .children
+            .context("Children were not looked up.")?
+            .into_iter()
+            .filter(|c| c.optimal)
+            .map(|c| c.encoding)
+            .collect();
+
+        assert_eq!(optimal, vec!["1-1"]);
+        Ok(())
+    }
+
+    #[test]
+    fn random_states_are_distinct_and_reproducible() -> Result<()> {
+        let conn = test::database()?;
+        let schema = SchemaBuilder::new("query_test")
+            .players(1)
+            .key("state", "INTEGER")
+            .column("remoteness", "INTEGER")
+            .column("player", "INTEGER")
+            .build()?;
+
+        conn.execute(&schema.create_table_query(), [])?;
+        for key in 0..20_i64 {
+            conn.execute(
+                &schema.insert_query(),
+                rusqlite::params![key, 0, 0, 0],
+            )?;
+        }
+
+        let choose = |count, seed| {
+            choose::<8, zero_by::Session>(
+                &conn,
+                &schema,
+                count,
+                &mut Rng::with_seed(seed),
+            )
+        };
+
+        let mut states = choose(8, 0)?;
+        assert_eq!(states, choose(8, 0)?);
+        states.sort();
+        states.dedup();
+        assert_eq!(states.len(), 8);
+        assert_eq!(choose(50, 1)?.len(), 20);
+        Ok(())
+    }
+}
@@ -0,0 +1,448 @@
+//! # Position Sampling Module
+//!
+//! This module provides a way of generating datasets of game states for game
+//! variants which are too large to solve. States are collected from playouts
+//! starting at the variant's starting state, and written out with the same
+//! layout as exported solution tables, where utility values (if any) are
+//! estimated through random rollouts or Monte Carlo tree search instead of
+//! being computed exactly.
+
+use anyhow::Context;
+use anyhow::Result;
+use fastrand::Rng;
+use rusqlite::Connection;
+use rusqlite::params_from_iter;
+use rusqlite::types::Value;
+
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use crate::game::Codec;
+use crate::game::Features;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::PlayerCount;
+use crate::game::State;
+use crate::interface::Estimator;
+use crate::interface::ExportFormat;
+use crate::interface::SamplePolicy;
+use crate::interface::export;
+use crate::interface::featurize;
+use crate::solver::Game;
+use crate::solver::IntegerUtility;
+use crate::solver::algorithm::mcts;
+use crate::solver::algorithm::rollout;
+use crate::solver::db;
+use crate::solver::db::Schema;
+use crate::solver::db::SchemaBuilder;
+
+/* CONSTANTS */
+
+/// Maximum number of playouts attempted per requested sample, which bounds
+/// the work done on game variants with fewer states than requested.
+const PLAYOUTS_PER_SAMPLE: usize = 16;
+
+/// Minimum weight given to a move by [`SamplePolicy::Weighted`], so that all
+/// moves retain some probability of being explored.
+const MIN_WEIGHT: f64 = 0.1;
+
+/* DEFINITIONS */
+
+/// Parameters for generating a dataset of sampled game states.
+pub struct Sampling {
+    /// Number of unique states to sample.
+    pub count: usize,
+
+    /// How moves are chosen during playouts.
+    pub policy: SamplePolicy,
+
+    /// How the utility of each state is estimated.
+    pub estimator: Estimator,
+
+    /// Number of random rollouts (or search iterations) used to estimate the
+    /// utility of each state, where zero means that utilities are not
+    /// estimated.
+    pub rollouts: usize,
+
+    /// Names of the game features to compute for each state, or all of them
+    /// if empty.
+    pub features: Vec<String>,
+
+    /// Number of threads used to compute features.
+    pub threads: NonZeroUsize,
+
+    /// Seed for the random number generator, for reproducible datasets.
+    pub seed: Option<u64>,
+}
+
+/// The origin of the utility values of a sampled state.
+enum ValueSource {
+    /// The state is terminal, so its utility is exact.
+    Terminal,
+
+    /// The utility was estimated through random rollouts.
+    Rollout,
+
+    /// The utility was estimated through a Monte Carlo tree search.
+    Search,
+
+    /// The utility was not computed.
+    Unknown,
+}
+
+/* API */
+
+/// Samples states of the game `variant` according to `options`, and writes
+/// them along with their features and estimated utilities to `output` (or to
+/// STDOUT if it is not provided) in the specified `format`.
+///
+/// # Errors
+///
+/// Fails if any of the requested features are not game features of `game`,
+/// or if the sampled dataset cannot be written.
+pub fn dataset<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    variant: &str,
+    options: &Sampling,
+    format: ExportFormat,
+    output: Option<&Path>,
+) -> Result<()>
+where
+    G: Information
+        + Codec<B>
+        + Implicit<B>
+        + Features<B>
+        + Game<N, B>
+        + IntegerUtility<N, B>
+        + Sync,
+{
+    let mut conn = Connection::open_in_memory()
+        .context("Failed to initialize in-memory sample database.")?;
+
+    let schema = sample::<N, B, G>(&mut conn, game, variant, options)?;
+    export::table(&conn, game, &schema, variant, format, output)
+}
+
+/* HELPERS */
+
+/// Samples states of `game` according to `options` into a new table in the
+/// database behind `conn`, returning the schema of that table.
+fn sample<const N: PlayerCount, const B: usize, G>(
+    conn: &mut Connection,
+    game: &G,
+    variant: &str,
+    options: &Sampling,
+) -> Result<Schema>
+where
+    G: Information
+        + Codec<B>
+        + Implicit<B>
+        + Features<B>
+        + Game<N, B>
+        + IntegerUtility<N, B>
+        + Sync,
+{
+    let table = format!("{}_{}_samples", G::info().name, variant);
+    let schema = SchemaBuilder::new(&table)
+        .players(N)
+        .key("state", db::key_type::<B>())
+        .nullable("remoteness", "INTEGER")
+        .column("player", "INTEGER")
+        .column("value_source", "TEXT")
+        .estimates()
+        .build()?;
+
+    conn.execute(&schema.drop_table_query(), [])?;
+    conn.execute(&schema.create_table_query(), [])?;
+
+    let mut rng = if let Some(seed) = options.seed {
+        Rng::with_seed(seed)
+    } else {
+        Rng::new()
+    };
+
+    let states = positions::<N, B, G>(game, options, &mut rng);
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(&schema.insert_query())?;
+        for state in states {
+            let (source, utility) = if game.sink(state) {
+                let exact = game.utility(state);
+                (
+                    ValueSource::Terminal,
+                    Some(exact.map(|u| u as f64)),
+                )
+            } else if options.rollouts > 0 {
+                let source = match options.estimator {
+                    Estimator::Rollout => ValueSource::Rollout,
+                    Estimator::Mcts => ValueSource::Search,
+                };
+
+                let budget = options.rollouts;
+                let value =
+                    estimate::<N, B, G>(game, state, options, budget, &mut rng);
+                (source, Some(value))
+            } else {
+                (ValueSource::Unknown, None)
+            };
+
+            let remoteness =
+                if game.sink(state) { Value::Integer(0) } else { Value::Null };
+
+            let mut row = vec![
+                db::key(&state),
+                remoteness,
+                Value::Integer(game.turn(state) as i64),
+                Value::Text(source.to_string()),
+            ];
+
+            for i in 0..N {
+                row.push(utility.map_or(Value::Null, |u| Value::Real(u[i])));
+            }
+
+            stmt.execute(params_from_iter(row))?;
+        }
+    }
+    tx.commit()?;
+
+    featurize::unsolved(
+        conn,
+        game,
+        &schema,
+        &options.features,
+        options.threads,
+    )
+    .context("Failed to compute features for sampled states.")?;
+
+    Ok(schema)
+}
+
+/// Returns up to `options.count` unique states of `game` visited by playouts
+/// from its starting state, in the order they were first visited.
+fn positions<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    options: &Sampling,
+    rng: &mut Rng,
+) -> Vec<State<B>>
+where
+    G: Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    let mut seen = HashSet::new();
+    let mut out = Vec::with_capacity(options.count);
+    let budget = options
+        .count
+        .saturating_mul(PLAYOUTS_PER_SAMPLE);
+
+    for _ in 0..budget {
+        let mut curr = game.source();
+        loop {
+            if out.len() >= options.count {
+                return out;
+            }
+
+            if seen.insert(curr) {
+                out.push(curr);
+            }
+
+            if game.sink(curr) {
+                break;
+            }
+
+            let children = game.adjacent(curr);
+            curr = match options.policy {
+                SamplePolicy::Uniform => children[rng.usize(..children.len())],
+                SamplePolicy::Weighted => {
+                    weighted::<N, B, G>(game, curr, &children, options, rng)
+                },
+            };
+        }
+    }
+
+    out
+}
+
+/// Chooses one of `children` of `state` at random, where each is weighted by
+/// the utility for the player to move at `state` estimated according to
+/// `options` (with at least one rollout), rescaled to the unit interval across
+/// all `children`.
+fn weighted<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    children: &[State<B>],
+    options: &Sampling,
+    rng: &mut Rng,
+) -> State<B>
+where
+    G: Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    let turn = game.turn(state);
+    let rollouts = options.rollouts.max(1);
+    let values: Vec<f64> = children
+        .iter()
+        .map(|&c| estimate::<N, B, G>(game, c, options, rollouts, rng)[turn])
+        .collect();
+
+    let max = values
+        .iter()
+        .copied()
+        .fold(f64::MIN, f64::max);
+
+    let min = values
+        .iter()
+        .copied()
+        .fold(f64::MAX, f64::min);
+
+    let weights: Vec<f64> =
+        values
+            .iter()
+            .map(|v| {
+                if max > min {
+                    (v - min) / (max - min) + MIN_WEIGHT
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+
+    let mut target = rng.f64() * weights.iter().sum::<f64>();
+    for (i, w) in weights.iter().enumerate() {
+        if target < *w {
+            return children[i];
+        }
+        target -= w;
+    }
+    children[children.len() - 1]
+}
+
+/// Returns the utility vector of `state` estimated by `options.estimator`
+/// with `budget` rollouts or search iterations.
+fn estimate<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    options: &Sampling,
+    budget: usize,
+    rng: &mut Rng,
+) -> [f64; N]
+where
+    G: Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    match options.estimator {
+        Estimator::Rollout => {
+            rollout::estimate::<N, B, G>(game, state, budget, rng)
+        },
+        Estimator::Mcts => {
+            let config = mcts::Config {
+                iterations: budget,
+                ..Default::default()
+            };
+
+            mcts::evaluate::<N, B, G>(game, state, &config, rng)
+        },
+    }
+}
+
+/* AUXILIARY IMPLEMENTATIONS */
+
+impl std::fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueSource::Terminal => write!(f, "terminal"),
+            ValueSource::Rollout => write!(f, "rollout"),
+            ValueSource::Search => write!(f, "mcts"),
+            ValueSource::Unknown => write!(f, "none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::Variable;
+    use crate::game::mnk;
+    use crate::game::zero_by;
+
+    fn options(count: usize, policy: SamplePolicy) -> Sampling {
+        Sampling {
+            count,
+            policy,
+            estimator: Estimator::Rollout,
+            rollouts: 4,
+            features: vec!["ply".into()],
+            threads: NonZeroUsize::MIN,
+            seed: Some(42),
+        }
+    }
+
+    #[test]
+    fn samples_are_unique_and_reproducible() {
+        let game = mnk::Session::default();
+        for policy in [SamplePolicy::Uniform, SamplePolicy::Weighted] {
+            let opts = options(50, policy);
+            let first =
+                positions::<2, 8, _>(&game, &opts, &mut Rng::with_seed(1));
+            let again =
+                positions::<2, 8, _>(&game, &opts, &mut Rng::with_seed(1));
+            let unique: HashSet<_> = first.iter().collect();
+            assert_eq!(first.len(), 50);
+            assert_eq!(unique.len(), 50);
+            assert_eq!(first, again);
+        }
+    }
+
+    #[test]
+    fn sampled_table_records_value_sources() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        let game = mnk::Session::variant("2-2-2".into())?;
+        let opts = options(1000, SamplePolicy::Uniform);
+        let schema = sample::<2, 8, _>(&mut conn, &game, "2-2-2", &opts)?;
+
+        let count = |source: &str| -> Result<i64> {
+            Ok(conn.query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {} WHERE value_source = ?",
+                    schema.table()
+                ),
+                [source],
+                |row| row.get(0),
+            )?)
+        };
+
+        let terminal = count("terminal")?;
+        let rollout = count("rollout")?;
+        assert!(terminal > 0);
+        assert!(rollout > 0);
+        assert_eq!(count("none")?, 0);
+
+        let plies: i64 = conn.query_row(
+            &format!("SELECT COUNT(ply) FROM {}", schema.table()),
+            [],
+            |row| row.get(0),
+        )?;
+
+        assert_eq!(plies, terminal + rollout);
+        Ok(())
+    }
+
+    #[test]
+    fn search_estimates_are_recorded() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        let game = zero_by::Session::variant("3-6-1-2".into())?;
+        let mut opts = options(10, SamplePolicy::Weighted);
+        opts.estimator = Estimator::Mcts;
+        opts.features = Vec::new();
+        let schema = sample::<3, 8, _>(&mut conn, &game, "3-6-1-2", &opts)?;
+        let searched: i64 = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {} WHERE value_source = 'mcts'",
+                schema.table()
+            ),
+            [],
+            |row| row.get(0),
+        )?;
+
+        assert!(searched > 0);
+        Ok(())
+    }
+}
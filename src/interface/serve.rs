@@ -0,0 +1,420 @@
+//! # HTTP Server Module
+//!
+//! This module serves the game offerings and the solution tables in the game
+//! database through a small JSON API over a loopback address, so that local
+//! programs (such as graphical front-ends) can use them without going through
+//! the command line. Only `GET` requests are supported, on the routes below:
+//!
+//! - `/games`: Listing of all available games.
+//! - `/games/{game}`: All information about a game. See [`GameData`].
+//! - `/games/{game}/position?state=...`: Solved attributes of a state.
+//! - `/games/{game}/moves?state=...`: Solved attributes of a state, along with
+//!   those of the states reachable from it in one move.
+//! - `/games/{game}/sample?count=...&seed=...`: Solved attributes of up to
+//!   `count` (10 by default) states chosen at random.
+//!
+//! Routes under a game also accept a `variant` parameter, and use the game's
+//! default variant if it is not provided. Variants of games defined by files
+//! must name a file in the directory of game files given to the server, so
+//! that requests cannot read any other files. Failed requests are answered
+//! with a JSON object holding an explanation in its `error` field.
+//!
+//! Browsers only let web pages read responses from the server if it is asked
+//! to allow requests from any origin, since any page could otherwise query it.
+//!
+//! [`GameData`]: crate::game::GameData
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use clap::ValueEnum;
+use percent_encoding::percent_decode_str;
+use serde_json::Value;
+use serde_json::json;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Response;
+use tiny_http::Server;
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::path::Path;
+
+use crate::game::GameModule;
+use crate::game::registry;
+use crate::interface::InfoFormat;
+use crate::interface::session::DynSession;
+use crate::interface::util;
+
+/* CONSTANTS */
+
+/// Number of states returned by the sampling route if no count is requested.
+const DEFAULT_SAMPLE: usize = 10;
+
+/// Maximum number of states returned by a single request to the sampling
+/// route, which looks up each state in the database.
+const MAX_SAMPLE: usize = 1000;
+
+/* DEFINITIONS */
+
+/// Reasons for which a request can fail, which determine its status code.
+#[derive(Debug)]
+enum Failure {
+    /// The requested route or game does not exist.
+    Missing(String),
+
+    /// The request is malformed, or cannot be fulfilled with the contents of
+    /// the game database.
+    Invalid(anyhow::Error),
+}
+
+/* API */
+
+/// Answers requests to the routes described in the module documentation on
+/// `port` of the loopback address, until the process is terminated. Variants
+/// of games defined by files are read from the directory `games`, and are not
+/// available if it is not provided. Responses carry a wildcard CORS header
+/// only if `cors` is true.
+///
+/// # Errors
+///
+/// Fails if the server cannot listen on the requested port.
+pub fn run(port: u16, games: Option<&Path>, cors: bool) -> Result<()> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let server = Server::http(address)
+        .map_err(|e| anyhow!(e))
+        .context(format!("Failed to listen on {address}."))?;
+
+    println!("Serving on http://{address}");
+    for request in server.incoming_requests() {
+        let (status, body) = if *request.method() == Method::Get {
+            respond(request.url(), games)
+        } else {
+            (
+                405,
+                json!({ "error": "Only GET requests are supported." }),
+            )
+        };
+
+        let mut response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(header("Content-Type", "application/json"));
+
+        if cors {
+            response.add_header(header("Access-Control-Allow-Origin", "*"));
+        }
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {e}");
+        }
+    }
+    Ok(())
+}
+
+/* ROUTING */
+
+/// Returns the status code and JSON body of the response to a `GET` request
+/// for `url`, which includes its query string, reading game files from the
+/// directory `games`.
+fn respond(url: &str, games: Option<&Path>) -> (u16, Value) {
+    match route(url, games) {
+        Ok(body) => (200, body),
+        Err(Failure::Missing(hint)) => (404, json!({ "error": hint })),
+        Err(Failure::Invalid(e)) => (400, json!({ "error": format!("{e:#}") })),
+    }
+}
+
+/// Dispatches the request for `url` to the handler of its route.
+fn route(url: &str, games: Option<&Path>) -> Result<Value, Failure> {
+    let (path, query) = url
+        .split_once('?')
+        .unwrap_or((url, ""));
+
+    let params = parameters(query)?;
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    match segments.as_slice() {
+        ["games"] => listing(),
+        ["games", game] => info(game),
+        ["games", game, "position"] => position(game, &params, games, false),
+        ["games", game, "moves"] => position(game, &params, games, true),
+        ["games", game, "sample"] => sample(game, &params, games),
+        _ => Err(Failure::Missing(format!(
+            "There is no route '{path}'."
+        ))),
+    }
+}
+
+/* HANDLERS */
+
+/// Returns the listing of all available games.
+fn listing() -> Result<Value, Failure> {
+    let out = util::format_game_listing(&registry::GAMES, InfoFormat::Json)
+        .map_err(Failure::Invalid)?;
+
+    parse(&out)
+}
+
+/// Returns all information about `game`.
+fn info(game: &str) -> Result<Value, Failure> {
+    let data = (registry::entry(module(game)?).info)();
+    let out = util::aggregate_and_format_all_attributes(data, InfoFormat::Json)
+        .map_err(Failure::Invalid)?;
+
+    parse(&out)
+}
+
+/// Returns the solved attributes of the state in `params`, along with those of
+/// its children if `children` is true.
+fn position(
+    game: &str,
+    params: &HashMap<String, String>,
+    games: Option<&Path>,
+    children: bool,
+) -> Result<Value, Failure> {
+    let session = session(game, params, games)?;
+    let state = params
+        .get("state")
+        .cloned()
+        .ok_or_else(|| {
+            Failure::Invalid(anyhow!("Missing 'state' query parameter."))
+        })?;
+
+    let out = session
+        .query(state, children, InfoFormat::Json)
+        .map_err(Failure::Invalid)?;
+
+    parse(&out)
+}
+
+/// Returns the solved attributes of states chosen at random according to the
+/// `count` and `seed` in `params`.
+fn sample(
+    game: &str,
+    params: &HashMap<String, String>,
+    games: Option<&Path>,
+) -> Result<Value, Failure> {
+    let session = session(game, params, games)?;
+    let count = match params.get("count") {
+        None => DEFAULT_SAMPLE,
+        Some(c) => match c.parse() {
+            Ok(c) if c <= MAX_SAMPLE => c,
+            _ => Err(Failure::Invalid(anyhow!(
+                "The 'count' query parameter must be an integer no greater \
+                than {MAX_SAMPLE}."
+            )))?,
+        },
+    };
+
+    let seed = params
+        .get("seed")
+        .map(|s| s.parse::<u64>())
+        .transpose()
+        .map_err(|_| {
+            Failure::Invalid(anyhow!(
+                "The 'seed' query parameter must be a non-negative integer."
+            ))
+        })?;
+
+    let positions = session
+        .random(count, seed, InfoFormat::Json)
+        .map_err(Failure::Invalid)?
+        .iter()
+        .map(|out| parse(out))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Value::Array(positions))
+}
+
+/* HELPERS */
+
+/// Returns the game module named `game`.
+fn module(game: &str) -> Result<GameModule, Failure> {
+    GameModule::from_str(game, false)
+        .map_err(|_| Failure::Missing(format!("There is no game '{game}'.")))
+}
+
+/// Returns a session of `game` under the variant in `params`, or under its
+/// default variant if there is none. Variants naming game files must name a
+/// file in the directory `games`.
+fn session(
+    game: &str,
+    params: &HashMap<String, String>,
+    games: Option<&Path>,
+) -> Result<Box<dyn DynSession>, Failure> {
+    let entry = registry::entry(module(game)?);
+    let variant = match params.get("variant") {
+        Some(v) if entry.files && v != (entry.info)().variant_default => {
+            Some(file(v, games)?)
+        },
+        other => other.cloned(),
+    };
+
+    (entry.session)(variant).map_err(Failure::Invalid)
+}
+
+/// Returns the path to the game file `name` within the directory `games`,
+/// failing if there is no such directory or if the file is outside of it.
+fn file(name: &str, games: Option<&Path>) -> Result<String, Failure> {
+    let games = games.ok_or_else(|| {
+        Failure::Invalid(anyhow!(
+            "Game files are only available if the server is started with a \
+            directory of game files."
+        ))
+    })?;
+
+    let missing = || {
+        Failure::Invalid(anyhow!(
+            "There is no game file '{name}' in the directory of game files."
+        ))
+    };
+
+    let games = games
+        .canonicalize()
+        .map_err(|_| missing())?;
+
+    let path = games
+        .join(name)
+        .canonicalize()
+        .map_err(|_| missing())?;
+
+    if path.starts_with(&games) && path.is_file() {
+        Ok(path.to_string_lossy().into_owned())
+    } else {
+        Err(missing())
+    }
+}
+
+/// Returns the decoded key-value pairs of the URL `query` string.
+fn parameters(query: &str) -> Result<HashMap<String, String>, Failure> {
+    let decode = |s: &str| {
+        percent_decode_str(&s.replace('+', " "))
+            .decode_utf8()
+            .map(|s| s.into_owned())
+            .map_err(|_| {
+                Failure::Invalid(anyhow!("Query string is not valid UTF-8."))
+            })
+    };
+
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (key, value) = p
+                .split_once('=')
+                .unwrap_or((p, ""));
+            Ok((decode(key)?, decode(value)?))
+        })
+        .collect()
+}
+
+/// Parses the JSON output of an interface routine into a value.
+fn parse(out: &str) -> Result<Value, Failure> {
+    serde_json::from_str(out)
+        .context("Failed to parse generated JSON.")
+        .map_err(Failure::Invalid)
+}
+
+/// Returns an HTTP header with the given `name` and `value`.
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name, value).expect("Header must be valid ASCII.")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::test;
+    use crate::test::DevelopmentData;
+
+    /// Used for storing the game files served in tests under their own
+    /// subdirectory.
+    const MODULE_NAME: &str = "serve-tests";
+
+    #[test]
+    fn query_strings_are_decoded() -> Result<(), Failure> {
+        let params = parameters("state=3-0&variant=a%2Fb+c&children")?;
+        assert_eq!(params["state"], "3-0");
+        assert_eq!(params["variant"], "a/b c");
+        assert_eq!(params["children"], "");
+        assert!(parameters("").unwrap().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn game_information_is_served() {
+        let (status, body) = respond("/games", None);
+        assert_eq!(status, 200);
+        assert_eq!(
+            body.as_array().map(Vec::len),
+            Some(registry::GAMES.len())
+        );
+
+        let (status, body) = respond("/games/zero-by/", None);
+        assert_eq!(status, 200);
+        assert_eq!(body["name"], "zero-by");
+    }
+
+    #[test]
+    fn bad_requests_are_rejected() {
+        assert_eq!(respond("/", None).0, 404);
+        assert_eq!(respond("/games/zero-by/nothing", None).0, 404);
+        assert_eq!(respond("/games/nothing", None).0, 404);
+        assert_eq!(
+            respond("/games/nothing/position?state=0", None).0,
+            404
+        );
+
+        let (status, body) = respond("/games/zero-by/position", None);
+        assert_eq!(status, 400);
+        assert!(body["error"].is_string());
+
+        assert_eq!(
+            respond("/games/zero-by/moves?variant=x&state=0", None).0,
+            400
+        );
+        assert_eq!(
+            respond("/games/zero-by/sample?count=-1", None).0,
+            400
+        );
+        assert_eq!(
+            respond("/games/zero-by/sample?seed=x", None).0,
+            400
+        );
+    }
+
+    #[test]
+    fn game_files_are_only_read_from_their_directory() -> Result<()> {
+        let games = test::get_directory(
+            DevelopmentData::Files,
+            PathBuf::from(MODULE_NAME),
+        )?;
+
+        fs::write(games.join("game.json"), "{}")?;
+        assert!(file("game.json", Some(&games)).is_ok());
+        assert!(file("game.json", None).is_err());
+        assert!(file("missing.json", Some(&games)).is_err());
+        assert!(file("../../../Cargo.toml", Some(&games)).is_err());
+        assert!(file("/dev/zero", Some(&games)).is_err());
+
+        let (status, body) = respond(
+            "/games/graph/position?state=a&variant=/dev/zero",
+            None,
+        );
+
+        assert_eq!(status, 400);
+        assert!(
+            body["error"]
+                .as_str()
+                .is_some_and(|e| e.contains("directory of game files"))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+//! # Session Interface Module
+//!
+//! This module provides access to every interface routine over a game session
+//! through trait objects, so that interfaces can work with any game offering
+//! in the [`registry`] without matching on each of them. Games do not need to
+//! implement anything here; wrapping a session of any game that provides the
+//! traits in [`game`] and [`solver`] in an [`Adapter`] is enough.
+//!
+//! [`registry`]: crate::game::registry
+//! [`game`]: crate::game
+//! [`solver`]: crate::solver
+
+use anyhow::Result;
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use crate::game::Codec;
+use crate::game::Features;
+use crate::game::Forward;
+use crate::game::GameData;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::Parameters;
+use crate::game::Player;
+use crate::game::util;
+use crate::interface::ExportFormat;
+use crate::interface::IOMode;
+use crate::interface::InfoFormat;
+use crate::interface::Opponent;
+use crate::interface::benchmark;
+use crate::interface::diff;
+use crate::interface::diff::Sides;
+use crate::interface::export;
+use crate::interface::featurize;
+use crate::interface::graph;
+use crate::interface::play;
+use crate::interface::query;
+use crate::interface::sample;
+use crate::interface::sample::Sampling;
+use crate::interface::verify;
+use crate::players;
+use crate::solver::Solvable;
+use crate::solver::Tabular;
+use crate::solver::algorithm::acyclic;
+use crate::solver::algorithm::mcts;
+
+/* DEFINITIONS */
+
+/// A game session whose states are encoded in `B` bytes, which provides every
+/// interface routine over it through [`DynSession`].
+pub struct Adapter<G, const B: usize>(pub G);
+
+/* INTERFACES */
+
+/// Provides every interface routine over a game session through a trait
+/// object, so that interfaces can work with any game in the [`registry`]. The
+/// states of sessions are also available through trait objects as slices of
+/// bytes, since sessions may differ in the size of their state encodings.
+///
+/// [`registry`]: crate::game::registry
+pub trait DynSession {
+    /// Returns useful information about the game family. See [`GameData`].
+    ///
+    /// This is an object-safe counterpart to [`Information::info`], provided
+    /// so that interfaces can work over any game offering through a trait
+    /// object.
+    fn data(&self) -> GameData;
+
+    /// Returns the bytes of the starting state of this game variant. See
+    /// [`Implicit::source`].
+    fn start(&self) -> Vec<u8>;
+
+    /// Returns true if the state with bytes `state` is terminal. See
+    /// [`Implicit::sink`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `state` is not the size of this session's state encodings.
+    fn terminal(&self, state: &[u8]) -> Result<bool>;
+
+    /// Returns the bytes of the states adjacent to the state with bytes
+    /// `state`. See [`Implicit::adjacent`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `state` is not the size of this session's state encodings.
+    fn children(&self, state: &[u8]) -> Result<Vec<Vec<u8>>>;
+
+    /// Returns the string encoding of the state with bytes `state`. See
+    /// [`Codec::encode`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `state` is not the size of this session's state encodings,
+    /// or under the same conditions as [`Codec::encode`].
+    fn show(&self, state: &[u8]) -> Result<String>;
+
+    /// Returns the bytes of the state encoded by `encoding`. See
+    /// [`Codec::decode`].
+    ///
+    /// # Errors
+    ///
+    /// Fails under the same conditions as [`Codec::decode`].
+    fn read(&self, encoding: String) -> Result<Vec<u8>>;
+
+    /// Advances the game's starting state to the last state in `history`. See
+    /// [`Forward::forward`] for the verification this entails.
+    ///
+    /// # Errors
+    ///
+    /// Fails under the same conditions as [`Forward::forward`].
+    fn advance(&mut self, history: Vec<String>) -> Result<()>;
+
+    /// Computes the solution to this game variant and persists it to the game
+    /// database according to `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the variant's player count is not supported by the solver, or
+    /// if there is any issue interacting with the game database.
+    fn solve(&mut self, mode: IOMode) -> Result<()>;
+
+    /// Writes the solution table of this game variant in `format` to `output`,
+    /// or to STDOUT if no path is provided. See [`export::dataset`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game variant has not been solved, or if writing fails.
+    fn export(&self, format: ExportFormat, output: Option<&Path>)
+    -> Result<()>;
+
+    /// Returns the solved attributes of the state encoded by `state`, and
+    /// those of its children if `children` is true, rendered in `format`. See
+    /// [`query::position`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `state` is malformed or if it is not in the solution table.
+    fn query(
+        &self,
+        state: String,
+        children: bool,
+        format: InfoFormat,
+    ) -> Result<String>;
+
+    /// Returns the solved attributes of up to `count` distinct states chosen
+    /// at random (using `seed`, if provided) from the solution table of this
+    /// game variant, each rendered in `format`. See [`query::random`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game variant has not been solved.
+    fn random(
+        &self,
+        count: usize,
+        seed: Option<u64>,
+        format: InfoFormat,
+    ) -> Result<Vec<String>>;
+
+    /// Starts an interactive game on the terminal where `humans` play against
+    /// `opponent`, which searches according to `search` if it is not the
+    /// solution of this game variant. See [`play::interactive`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if playing against the solution and the game variant has not
+    /// been solved.
+    fn play(
+        &self,
+        humans: Vec<Player>,
+        opponent: Opponent,
+        search: mcts::Config,
+    ) -> Result<()>;
+
+    /// Computes the features in `names` (or all features if it is empty) for
+    /// each state in the solution table of this game variant using up to
+    /// `threads` threads, storing them in columns of the same name. Only
+    /// missing values are computed unless `overwrite` is true. See
+    /// [`featurize::columns`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of `names` is not a feature of this game, or if the game
+    /// variant has not been solved.
+    fn featurize(
+        &self,
+        names: Vec<String>,
+        overwrite: bool,
+        threads: NonZeroUsize,
+    ) -> Result<()>;
+
+    /// Writes a dataset of states of this game variant sampled from playouts
+    /// according to `options` in `format` to `output`, or to STDOUT if no path
+    /// is provided. This does not require the game variant to be solved. See
+    /// [`sample::dataset`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the requested features is not a game feature of this
+    /// game, or if writing fails.
+    fn sample(
+        &self,
+        options: &Sampling,
+        format: ExportFormat,
+        output: Option<&Path>,
+    ) -> Result<()>;
+
+    /// Prints how far the moves chosen by searches configured by `search` are
+    /// from the solution of this game variant, over up to `positions` random
+    /// non-terminal states. See [`benchmark::mcts`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game variant has not been solved.
+    fn benchmark(
+        &self,
+        search: mcts::Config,
+        positions: usize,
+        seed: Option<u64>,
+        format: InfoFormat,
+    ) -> Result<()>;
+
+    /// Checks that the solution table of this game variant is consistent with
+    /// the game and with itself, printing any violations in `format`. See
+    /// [`verify::solution`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game variant has not been solved, or if any violation is
+    /// found.
+    fn verify(&self, format: InfoFormat) -> Result<()>;
+
+    /// Compares two solution tables of this game variant located by `sides`,
+    /// printing a summary of their differences in `format` which lists every
+    /// differing state if `full` is true. See [`diff::tables`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if either table does not exist.
+    fn diff(&self, sides: Sides, full: bool, format: InfoFormat) -> Result<()>;
+
+    /// Prints the graph of states reachable from the starting state of this
+    /// game variant in at most `depth` moves (or all of them, if not provided)
+    /// in DOT format, colored by solved values if the variant has been solved.
+    /// See [`graph::dot`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game database is not available.
+    fn graph(&self, depth: Option<usize>) -> Result<()>;
+}
+
+/* IMPLEMENTATIONS */
+
+impl<G, const B: usize> DynSession for Adapter<G, B>
+where
+    G: Information
+        + Parameters
+        + Codec<B>
+        + Implicit<B>
+        + Forward<B>
+        + Features<B>
+        + Tabular
+        + Solvable<B>
+        + Sync,
+{
+    fn data(&self) -> GameData {
+        G::info()
+    }
+
+    fn start(&self) -> Vec<u8> {
+        self.0.source().to_vec()
+    }
+
+    fn terminal(&self, state: &[u8]) -> Result<bool> {
+        Ok(self.0.sink(util::bytes(state)?))
+    }
+
+    fn children(&self, state: &[u8]) -> Result<Vec<Vec<u8>>> {
+        Ok(self
+            .0
+            .adjacent(util::bytes(state)?)
+            .iter()
+            .map(|s| s.to_vec())
+            .collect())
+    }
+
+    fn show(&self, state: &[u8]) -> Result<String> {
+        self.0.encode(util::bytes(state)?)
+    }
+
+    fn read(&self, encoding: String) -> Result<Vec<u8>> {
+        Ok(self.0.decode(encoding)?.to_vec())
+    }
+
+    fn advance(&mut self, history: Vec<String>) -> Result<()> {
+        self.0.forward(history)
+    }
+
+    fn solve(&mut self, mode: IOMode) -> Result<()> {
+        let game = &mut self.0;
+        players!(game.player_count(), N => {
+            acyclic::solve::<N, B, _>(game, mode)
+        })
+    }
+
+    fn export(
+        &self,
+        format: ExportFormat,
+        output: Option<&Path>,
+    ) -> Result<()> {
+        let game = &self.0;
+        let variant = game.variant_string();
+        export::dataset(game, game.schema(), variant, format, output)
+    }
+
+    fn query(
+        &self,
+        state: String,
+        children: bool,
+        format: InfoFormat,
+    ) -> Result<String> {
+        query::position(&self.0, self.0.schema(), state, children, format)
+    }
+
+    fn random(
+        &self,
+        count: usize,
+        seed: Option<u64>,
+        format: InfoFormat,
+    ) -> Result<Vec<String>> {
+        query::random(&self.0, self.0.schema(), count, seed, format)
+    }
+
+    fn play(
+        &self,
+        humans: Vec<Player>,
+        opponent: Opponent,
+        search: mcts::Config,
+    ) -> Result<()> {
+        let (game, schema) = (&self.0, self.0.schema());
+        players!(game.player_count(), N => {
+            play::interactive::<N, B, _>(game, schema, humans, opponent, search)
+        })
+    }
+
+    fn featurize(
+        &self,
+        names: Vec<String>,
+        overwrite: bool,
+        threads: NonZeroUsize,
+    ) -> Result<()> {
+        let game = &self.0;
+        featurize::columns(game, game.schema(), &names, overwrite, threads)
+    }
+
+    fn sample(
+        &self,
+        options: &Sampling,
+        format: ExportFormat,
+        output: Option<&Path>,
+    ) -> Result<()> {
+        let (game, v) = (&self.0, self.0.variant_string());
+        players!(game.player_count(), N => {
+            sample::dataset::<N, B, _>(game, v, options, format, output)
+        })
+    }
+
+    fn benchmark(
+        &self,
+        search: mcts::Config,
+        positions: usize,
+        seed: Option<u64>,
+        format: InfoFormat,
+    ) -> Result<()> {
+        let (game, schema) = (&self.0, self.0.schema());
+        players!(game.player_count(), N => {
+            benchmark::mcts::<N, B, _>(game, schema, search, positions, seed, format)
+        })
+    }
+
+    fn verify(&self, format: InfoFormat) -> Result<()> {
+        let (game, schema) = (&self.0, self.0.schema());
+        players!(game.player_count(), N => {
+            verify::solution::<N, B, _>(game, schema, format)
+        })
+    }
+
+    fn diff(&self, sides: Sides, full: bool, format: InfoFormat) -> Result<()> {
+        diff::tables(&self.0, self.0.schema(), sides, full, format)
+    }
+
+    fn graph(&self, depth: Option<usize>) -> Result<()> {
+        graph::dot(&self.0, self.0.schema(), depth)
+    }
+}
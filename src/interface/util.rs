@@ -4,9 +4,11 @@
 //! `crate::interface` module. The contents here are unstructured.
 
 use anyhow::{Context, Result};
-use serde_json::{Map, Value};
+use rusqlite::types::ValueRef;
+use serde_json::{Map, Number, Value};
 
 use crate::game::GameData;
+use crate::game::registry::Entry;
 
 use super::{GameAttribute, InfoFormat};
 
@@ -62,6 +64,96 @@ pub fn aggregate_and_format_all_attributes(
     aggregate_and_format_attributes(data, GAME_ATTRIBUTES.to_vec(), format)
 }
 
+/// Collects the name, description, default variant, and supported player
+/// counts of each of the registry `entries` into a single string in a
+/// specific `format`.
+pub fn format_game_listing(
+    entries: &[Entry],
+    format: InfoFormat,
+) -> Result<String> {
+    match format {
+        InfoFormat::Legible => {
+            let mut output = String::new();
+            entries.iter().for_each(|e| {
+                let data = (e.info)();
+                output += &format!(
+                    "{}\n\tabout: {}\n\tdefault variant: {}\n\tplayers: \
+                    {}-{}\n\n",
+                    data.name,
+                    data.about,
+                    data.variant_default,
+                    e.players.start(),
+                    e.players.end(),
+                )
+            });
+            Ok(output)
+        },
+        InfoFormat::Json => {
+            let games: Vec<Value> = entries
+                .iter()
+                .map(|e| {
+                    let data = (e.info)();
+                    let mut map = Map::new();
+                    map.insert("name".into(), data.name.into());
+                    map.insert("about".into(), data.about.into());
+                    map.insert(
+                        "variant-default".into(),
+                        data.variant_default.into(),
+                    );
+                    map.insert(
+                        "min-players".into(),
+                        (*e.players.start()).into(),
+                    );
+                    map.insert("max-players".into(), (*e.players.end()).into());
+                    Value::Object(map)
+                })
+                .collect();
+
+            let mut out = serde_json::to_string(&games)
+                .context("Failed to generate JSON array from game listing.")?;
+
+            out.push('\n');
+            Ok(out)
+        },
+    }
+}
+
+/* VALUE CONVERSIONS */
+
+/// Returns the JSON representation of an arbitrary database value.
+pub fn json_value(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::Number(i.into()),
+        ValueRef::Real(f) => Number::from_f64(f)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        ValueRef::Text(t) => {
+            Value::String(String::from_utf8_lossy(t).into_owned())
+        },
+        ValueRef::Blob(b) => Value::String(hex(b)),
+    }
+}
+
+/// Returns a human-readable representation of an arbitrary database value.
+pub fn legible_value(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => "null".into(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => f.to_string(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned(),
+        ValueRef::Blob(b) => hex(b),
+    }
+}
+
+/// Returns the lowercase hexadecimal representation of `bytes`.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,274 @@
+//! # Solution Verification Module
+//!
+//! This module re-reads a stored solution table and checks that it is
+//! internally consistent with the game it belongs to. That is, that every
+//! state reachable from the starting state is present, that the turn column
+//! agrees with [`Game::turn`], that terminal states hold the utility given by
+//! [`IntegerUtility::utility`], and that the utility and remoteness of every
+//! other state follow from those of its children under the policy used by
+//! the solvers (see [`crate::solver::algorithm::acyclic`]).
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use rusqlite::Connection;
+use serde_json::Map;
+
+use std::collections::HashSet;
+
+use crate::game;
+use crate::game::Codec;
+use crate::game::Implicit;
+use crate::game::Information;
+use crate::game::PlayerCount;
+use crate::game::State;
+use crate::interface::InfoFormat;
+use crate::solver::Game;
+use crate::solver::IUtility;
+use crate::solver::IntegerUtility;
+use crate::solver::Remoteness;
+use crate::solver::db;
+use crate::solver::db::Schema;
+use crate::solver::features::Table;
+
+/* DEFINITIONS */
+
+/// An inconsistency found in a solution table.
+#[derive(Debug, PartialEq)]
+struct Violation {
+    /// Encoding of the state at which the inconsistency was found.
+    state: String,
+
+    /// Explanation of the inconsistency.
+    reason: String,
+}
+
+/* API */
+
+/// Checks the solution table of `game` described by `schema` for consistency,
+/// printing every violation found to STDOUT in `format`.
+///
+/// # Errors
+///
+/// Fails if the game database is not available, if there is no solution
+/// table for the game variant, or if any violation is found.
+pub fn solution<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    schema: &Schema,
+    format: InfoFormat,
+) -> Result<()>
+where
+    G: Information + Codec<B> + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    let conn = game::util::database()
+        .context("Failed to obtain connection to game database.")?;
+
+    let (states, violations) = check::<N, B, G>(&conn, game, schema)?;
+    print!("{}", render(states, &violations, format)?);
+    if !violations.is_empty() {
+        bail!(
+            "Solution table '{}' failed verification with {} violation(s).",
+            schema.table(),
+            violations.len(),
+        )
+    }
+
+    Ok(())
+}
+
+/* HELPERS */
+
+/// Traverses every state of `game` reachable from its starting state, and
+/// checks its entry in the table of `schema` against the game and against
+/// the entries of its children. Returns the number of states visited along
+/// with all violations found.
+fn check<const N: PlayerCount, const B: usize, G>(
+    conn: &Connection,
+    game: &G,
+    schema: &Schema,
+) -> Result<(usize, Vec<Violation>)>
+where
+    G: Information + Codec<B> + Implicit<B> + Game<N, B> + IntegerUtility<N, B>,
+{
+    if !db::table_exists(conn, schema.table())? {
+        bail!(
+            "No solution table '{}' was found in the database. Try running \
+            'nova build {}' with the same variant first.",
+            schema.table(),
+            G::info().name,
+        )
+    }
+
+    let table = Table::new(conn, schema);
+    let mut violations = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![game.source()];
+    while let Some(state) = stack.pop() {
+        if !seen.insert(state) {
+            continue;
+        }
+
+        let children =
+            if game.sink(state) { Vec::new() } else { game.adjacent(state) };
+
+        stack.extend(children.iter().copied());
+        let mut violation = |reason: String| -> Result<()> {
+            violations.push(Violation {
+                state: game.encode(state)?,
+                reason,
+            });
+            Ok(())
+        };
+
+        let outcome = if let Some(outcome) = table.lookup(state)? {
+            outcome
+        } else {
+            violation("State is missing from the solution table.".into())?;
+            continue;
+        };
+
+        let turn = game.turn(state);
+        if outcome.player != turn {
+            violation(format!(
+                "Stored turn is {}, but the game reports {turn}.",
+                outcome.player
+            ))?;
+        }
+
+        let expected = if game.sink(state) {
+            Some((0, game.utility(state).to_vec()))
+        } else {
+            expected(&table, turn, &children)?
+        };
+
+        if let Some((remoteness, utility)) = expected {
+            if outcome.utility != utility {
+                violation(format!(
+                    "Stored utility is {:?}, but it should be {utility:?}.",
+                    outcome.utility
+                ))?;
+            }
+
+            if outcome.remoteness != remoteness {
+                violation(format!(
+                    "Stored remoteness is {}, but it should be {remoteness}.",
+                    outcome.remoteness
+                ))?;
+            }
+        }
+    }
+
+    Ok((seen.len(), violations))
+}
+
+/// Returns the remoteness and utility that a state whose turn is `turn` and
+/// whose children are `children` should have according to the stored entries
+/// of its children (see [`Table::best`]). Returns `None` if any child is
+/// missing from the table, which is reported when the child itself is visited.
+fn expected<const B: usize>(
+    table: &Table,
+    turn: usize,
+    children: &[State<B>],
+) -> Result<Option<(Remoteness, Vec<IUtility>)>> {
+    for &child in children {
+        if table.lookup(child)?.is_none() {
+            return Ok(None);
+        }
+    }
+
+    Ok(table
+        .best(turn, children)?
+        .map(|(_, b)| (b.remoteness + 1, b.utility)))
+}
+
+/// Returns the string representation of the result of verifying `states`
+/// states, which produced `violations`, in the given `format`.
+fn render(
+    states: usize,
+    violations: &[Violation],
+    format: InfoFormat,
+) -> Result<String> {
+    match format {
+        InfoFormat::Legible => {
+            let mut out = String::new();
+            for v in violations {
+                out += &format!("{}\n\t{}\n", v.state, v.reason);
+            }
+
+            out += &format!(
+                "Verified {states} state(s), found {} violation(s).\n",
+                violations.len()
+            );
+            Ok(out)
+        },
+        InfoFormat::Json => {
+            let violations = violations
+                .iter()
+                .map(|v| {
+                    let mut map = Map::new();
+                    map.insert("state".into(), v.state.clone().into());
+                    map.insert("reason".into(), v.reason.clone().into());
+                    serde_json::Value::Object(map)
+                })
+                .collect();
+
+            let mut map = Map::new();
+            map.insert("states".into(), states.into());
+            map.insert(
+                "violations".into(),
+                serde_json::Value::Array(violations),
+            );
+
+            let mut out = serde_json::to_string(&map)
+                .context("Failed to generate JSON object from violations.")?;
+
+            out.push('\n');
+            Ok(out)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test;
+
+    #[test]
+    fn consistent_solution_passes() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("verify_test")?;
+        let (states, violations) = check::<2, 8, _>(&conn, &game, &schema)?;
+        assert_eq!(states, 6);
+        assert!(violations.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn inconsistencies_are_reported() -> Result<()> {
+        let (conn, game, schema) = test::solved_zero_by("verify_test")?;
+        let key = |s: &str| -> Result<i64> {
+            Ok(i64::from_be_bytes(game.decode(s.into())?))
+        };
+
+        conn.execute(
+            "UPDATE verify_test SET remoteness = 5 WHERE state = ?",
+            [key("1-1")?],
+        )?;
+        conn.execute(
+            "DELETE FROM verify_test WHERE state = ?",
+            [key("0-1")?],
+        )?;
+
+        let (_, violations) = check::<2, 8, _>(&conn, &game, &schema)?;
+        let states: Vec<&str> = violations
+            .iter()
+            .map(|v| v.state.as_str())
+            .collect();
+
+        assert_eq!(violations.len(), 3);
+        assert!(states.contains(&"0-1"));
+        assert!(states.contains(&"1-1"));
+        assert!(states.contains(&"3-0"));
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+#![warn(missing_docs, deprecated)]
+//! # GamesmanNova
+//!
+//! Library behind the `nova` executable, which generates datasets of
+//! sequential game states and associated features. It is organized into the
+//! following modules, which can be used to implement and solve games outside
+//! of this crate:
+//!
+//! - [`game`]: Interfaces that games implement to be solved and explored,
+//!   along with the implementations of the games offered through `nova`.
+//! - [`solver`]: Solving algorithms over those interfaces, along with access
+//!   to the game database where their solutions are persisted.
+//! - [`interface`]: Routines that query, export, and otherwise consume game
+//!   solutions, along with the definitions of the command line interface.
+//!
+//! For example, a game defined in another crate only needs to implement the
+//! traits in [`game`] and [`solver`] that a solving algorithm requires in
+//! order to be solved by it.
+
+/* MODULES */
+
+#[cfg(test)]
+mod test;
+
+pub mod interface;
+pub mod solver;
+pub mod game;
+
+mod util;
+
+/* RE-EXPORTS */
+
+/// Used by exported macros, so that crates which use them need not depend on
+/// [`anyhow`] themselves.
+#[doc(hidden)]
+pub use anyhow;
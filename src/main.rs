@@ -1,44 +1,50 @@
 #![warn(missing_docs, deprecated)]
 //! # Execution Module
 //!
-//! The module which aggregates the libraries provided in `core`, `games`, and
-//! `interfaces` to provide an entry point to all the functionality of the
-//! project.
+//! The module which aggregates the modules provided by the `gamesman_nova`
+//! library in `game`, `solver`, and `interface` to provide a command line
+//! entry point to all the functionality of the project.
 //!
 //! Instead of this project's modules having an emphasized many-to-many
 //! relationship, greater weight is placed on making things fit into this
-//! module as a centralized point.
+//! module as a centralized point. All behavior should live in the library, so
+//! that this module only maps subcommands to library calls.
 
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
 
+use std::num::NonZeroUsize;
 use std::process;
 
-use crate::game::Forward;
-use crate::game::GameModule;
-use crate::game::Information;
-use crate::game::mnk;
-use crate::game::zero_by;
-use crate::interface::cli::*;
-
-/* MODULES */
-
-#[cfg(test)]
-mod test;
-
-mod interface;
-mod solver;
-mod game;
-mod util;
+use gamesman_nova::game::registry;
+use gamesman_nova::interface;
+use gamesman_nova::interface::IOMode;
+use gamesman_nova::interface::cli::*;
+use gamesman_nova::interface::config;
+use gamesman_nova::interface::diff::Sides;
+use gamesman_nova::interface::sample::Sampling;
+use gamesman_nova::solver::algorithm::mcts;
 
 /* PROGRAM ENTRY */
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    config::init(cli.db);
     let res = match cli.command {
         Commands::Info(args) => info(args),
+        Commands::List(args) => list(args),
         Commands::Build(args) => build(args),
+        Commands::Featurize(args) => featurize(args),
+        Commands::Export(args) => export(args),
+        Commands::Query(args) => query(args),
+        Commands::Play(args) => play(args),
+        Commands::Sample(args) => sample(args),
+        Commands::Benchmark(args) => benchmark(args),
+        Commands::Verify(args) => verify(args),
+        Commands::Diff(args) => diff(args),
+        Commands::Graph(args) => graph(args),
+        Commands::Serve(args) => serve(args),
     };
     if res.is_err() && cli.quiet {
         process::exit(exitcode::USAGE)
@@ -49,52 +55,173 @@ fn main() -> Result<()> {
 /* SUBCOMMAND EXECUTORS */
 
 fn build(args: BuildArgs) -> Result<()> {
-    match args.target {
-        GameModule::ZeroBy => {
-            let mut session = zero_by::Session::new(args.variant)?;
-            if args.forward {
-                let input = stdin_lines()
-                    .context("Failed to read STDIN history input.")?;
-
-                session
-                    .forward(input)
-                    .context("Failed to forward state with history input.")?
-            }
-
-            session
-                .solve(args.mode)
-                .context(format!(
-                    "Failed solver execution for {}.",
-                    zero_by::Session::info().name
-                ))?
-        },
-        GameModule::Mnk => {
-            let mut session = mnk::Session::new(args.variant)?;
-            if args.forward {
-                let input = stdin_lines()
-                    .context("Failed to read STDIN history input.")?;
-
-                session
-                    .forward(input)
-                    .context("Failed to forward state with history input.")?
-            }
-
-            session
-                .solve(args.mode)
-                .context(format!(
-                    "Failed solver execution for {}.",
-                    mnk::Session::info().name
-                ))?
-        },
+    let entry = registry::entry(args.target);
+    let mut session = (entry.session)(args.variant)?;
+    if args.forward {
+        let input =
+            stdin_lines().context("Failed to read STDIN history input.")?;
+
+        session
+            .advance(input)
+            .context("Failed to forward state with history input.")?
+    }
+
+    session
+        .solve(args.mode)
+        .context(format!(
+            "Failed solver execution for {}.",
+            session.data().name
+        ))?;
+
+    if args.mode != IOMode::Forgetful && !args.features.is_empty() {
+        session
+            .featurize(args.features, false, NonZeroUsize::MIN)
+            .context(format!(
+                "Failed to compute features for {}.",
+                session.data().name
+            ))?
     }
     Ok(())
 }
 
-fn info(args: InfoArgs) -> Result<()> {
-    let data = match args.target {
-        GameModule::ZeroBy => zero_by::Session::info(),
-        GameModule::Mnk => mnk::Session::info(),
+fn featurize(args: FeaturizeArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    session
+        .featurize(args.features, args.overwrite, args.threads)
+        .context(format!(
+            "Failed to compute features for {}.",
+            session.data().name
+        ))
+}
+
+fn export(args: ExportArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    session
+        .export(args.format, args.output.as_deref())
+        .context(format!(
+            "Failed to export dataset for {}.",
+            session.data().name
+        ))
+}
+
+fn query(args: QueryArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    let out = session
+        .query(args.state, args.children, args.output)
+        .context(format!(
+            "Failed to query position for {}.",
+            session.data().name
+        ))?;
+
+    print!("{out}");
+    Ok(())
+}
+
+fn play(args: PlayArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    let search = mcts::Config {
+        iterations: args.iterations,
+        exploration: args.exploration,
     };
+
+    session
+        .play(args.humans, args.opponent, search)
+        .context(format!(
+            "Failed to play a game of {}.",
+            session.data().name
+        ))
+}
+
+fn sample(args: SampleArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    let options = Sampling {
+        count: args.count,
+        policy: args.policy,
+        estimator: args.estimator,
+        rollouts: args.rollouts,
+        features: args.features,
+        threads: args.threads,
+        seed: args.seed,
+    };
+
+    session
+        .sample(&options, args.format, args.output.as_deref())
+        .context(format!(
+            "Failed to sample states of {}.",
+            session.data().name
+        ))
+}
+
+fn benchmark(args: BenchmarkArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    let search = mcts::Config {
+        iterations: args.iterations,
+        exploration: args.exploration,
+    };
+
+    session
+        .benchmark(search, args.positions, args.seed, args.output)
+        .context(format!(
+            "Failed to benchmark search on {}.",
+            session.data().name
+        ))
+}
+
+fn verify(args: VerifyArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    session
+        .verify(args.output)
+        .context(format!(
+            "Failed to verify solution for {}.",
+            session.data().name
+        ))
+}
+
+fn diff(args: DiffArgs) -> Result<()> {
+    let session = (registry::entry(args.target).session)(args.variant)?;
+    let sides = Sides {
+        left: args.table,
+        right: args.against_table,
+        database: args.against,
+    };
+
+    session
+        .diff(sides, args.full, args.output)
+        .context(format!(
+            "Failed to compare datasets for {}.",
+            session.data().name
+        ))
+}
+
+fn graph(args: GraphArgs) -> Result<()> {
+    let mut session = (registry::entry(args.target).session)(args.variant)?;
+    if args.forward {
+        let input =
+            stdin_lines().context("Failed to read STDIN history input.")?;
+
+        session
+            .advance(input)
+            .context("Failed to forward state with history input.")?
+    }
+
+    session
+        .graph(args.depth)
+        .context(format!(
+            "Failed to render the graph of {}.",
+            session.data().name
+        ))
+}
+
+fn serve(args: ServeArgs) -> Result<()> {
+    interface::serve::run(args.port, args.games.as_deref(), args.cors)
+}
+
+fn list(args: ListArgs) -> Result<()> {
+    interface::cli::format_and_output_game_listing(args.output)
+}
+
+fn info(args: InfoArgs) -> Result<()> {
+    let data = (registry::entry(args.target).info)();
     interface::cli::format_and_output_game_attributes(
         data,
         args.attributes,
@@ -96,7 +96,10 @@ where
                     }
                 }
             }
-        } else {
+        } else if !game.sink(curr) {
+            // Terminal states can be pushed more than once before they are
+            // solved (once for each parent that sees them unsolved), and they
+            // must not be solved again as if they were medial states.
             let mut next = Solution::default();
             let mut max_val = IUtility::MIN;
             let mut min_rem = Remoteness::MAX;
@@ -143,10 +146,23 @@ impl<const N: PlayerCount> Default for Solution<N> {
 mod test {
 
     use anyhow::Result;
+    use fastrand::Rng;
+    use rusqlite::Connection;
 
+    use std::collections::HashSet;
+
+    use crate::game::Codec;
+    use crate::game::Parameters;
+    use crate::game::State;
+    use crate::game::Variable;
+    use crate::game::mock::Generator;
     use crate::game::mock::Node;
     use crate::game::mock::SessionBuilder;
+    use crate::game::zero_by;
     use crate::node;
+    use crate::players;
+    use crate::solver::Tabular;
+    use crate::solver::features::Table;
     use crate::test;
 
     use super::*;
@@ -155,9 +171,13 @@ mod test {
     /// for testing purposes in this module under their own subdirectory.
     const MODULE_NAME: &str = "acyclic-solver-tests";
 
+    /// Number of random games of each player count that the solver is checked
+    /// against the reference solver on.
+    const RANDOM_GAMES: u64 = 1000;
+
     fn test_solve<const N: PlayerCount, const B: usize, G>(
         game: &mut G,
-    ) -> Result<()>
+    ) -> Result<Connection>
     where
         G: Implicit<B> + Game<N, B> + IntegerUtility<N, B> + Persistent<N, B>,
     {
@@ -184,9 +204,123 @@ mod test {
         tx.commit()
             .context("Failed to commit transaction.")?;
 
+        Ok(conn)
+    }
+
+    /// Returns the solution to `state` in `game` by exhaustively exploring
+    /// every path out of it, following the same policy as the solver (higher
+    /// utility for the player in turn, then lower remoteness, keeping the
+    /// first of any children which are equally good).
+    fn reference<const N: PlayerCount, G>(game: &G, state: State) -> Solution<N>
+    where
+        G: Implicit + Game<N> + IntegerUtility<N>,
+    {
+        let player = game.turn(state);
+        if game.sink(state) {
+            return Solution {
+                remoteness: 0,
+                utility: game.utility(state),
+                player,
+            };
+        }
+
+        let mut best: Option<Solution<N>> = None;
+        for child in game.adjacent(state) {
+            let solved = reference(game, child);
+            let better = best.as_ref().is_none_or(|b| {
+                solved.utility[player] > b.utility[player]
+                    || (solved.utility[player] == b.utility[player]
+                        && solved.remoteness < b.remoteness)
+            });
+
+            if better {
+                best = Some(solved);
+            }
+        }
+
+        let best = best.expect("Medial state has no children.");
+        Solution {
+            remoteness: best.remoteness + 1,
+            utility: best.utility,
+            player,
+        }
+    }
+
+    /// Solves `RANDOM_GAMES` random `N`-player games with the solver, and
+    /// checks that the solution to every reachable state agrees with the
+    /// reference solver.
+    fn matches_reference<const N: PlayerCount>() -> Result<()> {
+        for seed in 0..RANDOM_GAMES {
+            let blueprint = Generator::new(N)
+                .depth(6)
+                .width(5)
+                .payoff(2)
+                .generate(&mut Rng::with_seed(seed))?;
+
+            let mut game = blueprint.session("random")?;
+            let conn = test_solve::<N, 8, _>(&mut game)?;
+            let queries = Persistent::<N>::prepare(
+                &mut game,
+                &mut conn.unchecked_transaction()?,
+                IOMode::Constructive,
+            )?;
+
+            let mut select = conn.prepare(&queries.select)?;
+            let mut stack = vec![game.source()];
+            while let Some(state) = stack.pop() {
+                let solved = game
+                    .select(&mut select, &state)?
+                    .context("Reachable state was not solved.")?;
+
+                assert_eq!(
+                    solved,
+                    reference::<N, _>(&game, state),
+                    "Solutions disagree on random game {seed}."
+                );
+
+                stack.extend(game.adjacent(state));
+            }
+        }
         Ok(())
     }
 
+    /// Returns the number of states reachable in `game`, checking that the
+    /// solution to each of them in `conn` follows from those of its children.
+    fn check_consistent<const N: PlayerCount, G>(
+        conn: &Connection,
+        game: &G,
+    ) -> Result<usize>
+    where
+        G: Implicit + Game<N> + IntegerUtility<N> + Tabular,
+    {
+        let table = Table::new(conn, game.schema());
+        let mut seen = HashSet::new();
+        let mut stack = vec![game.source()];
+        while let Some(state) = stack.pop() {
+            if !seen.insert(state) {
+                continue;
+            }
+
+            let solved = table.outcome(state)?;
+            assert_eq!(solved.player, game.turn(state));
+            if game.sink(state) {
+                assert_eq!(solved.remoteness, 0);
+                assert_eq!(solved.utility, game.utility(state));
+                continue;
+            }
+
+            let children = game.adjacent(state);
+            let (_, best) = table
+                .best(solved.player, &children)?
+                .context("Medial state has no children.")?;
+
+            assert_eq!(solved.remoteness, best.remoteness + 1);
+            assert_eq!(solved.utility, best.utility);
+            stack.extend(children);
+        }
+        Ok(seen.len())
+    }
+
     #[test]
     fn acyclic_solver_on_sample1() -> Result<()> {
         let s1 = node!(0);
@@ -240,4 +374,57 @@ mod test {
         g.visualize(MODULE_NAME)?;
         Ok(())
     }
+
+    #[test]
+    fn terminal_states_reached_twice_keep_their_solution() -> Result<()> {
+        let s1 = node!(0);
+        let s2 = node!(1);
+        let t1 = node![0; 1, -1];
+
+        let mut g = SessionBuilder::new("sample3")
+            .edge(&s1, &s2)?
+            .edge(&s1, &t1)?
+            .edge(&s2, &t1)?
+            .label(&t1, "t1")?
+            .source(&s1)?
+            .build()?;
+
+        let conn = test_solve::<2, 8, _>(&mut g)?;
+        let queries = Persistent::<2>::prepare(
+            &mut g,
+            &mut conn.unchecked_transaction()?,
+            IOMode::Constructive,
+        )?;
+
+        let mut select = conn.prepare(&queries.select)?;
+        let sink = g.decode("t1".into())?;
+        let solved = g
+            .select(&mut select, &sink)?
+            .context("Terminal state was not solved.")?;
+
+        assert_eq!(solved.remoteness, 0);
+        assert_eq!(solved.utility, [1, -1]);
+        Ok(())
+    }
+
+    #[test]
+    fn acyclic_solver_matches_reference_on_random_games() -> Result<()> {
+        matches_reference::<1>()?;
+        matches_reference::<2>()?;
+        matches_reference::<3>()?;
+        matches_reference::<4>()?;
+        Ok(())
+    }
+
+    #[test]
+    fn acyclic_solver_on_player_counts_above_ten() -> Result<()> {
+        let mut game = zero_by::Session::variant("12-30-1-2-3".into())?;
+        let states = players!(game.player_count(), N => {
+            let conn = test_solve::<N, 8, _>(&mut game)?;
+            check_consistent::<N, _>(&conn, &game)
+        })?;
+
+        assert_eq!(states, 270);
+        Ok(())
+    }
 }
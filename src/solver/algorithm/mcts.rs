@@ -0,0 +1,263 @@
+//! # Monte Carlo Tree Search Module
+//!
+//! This module implements UCT (Upper Confidence bounds applied to Trees) over
+//! acyclic games with any number of players. Each node of the search tree
+//! keeps the sum of the utility vectors of the playouts that went through it,
+//! and players choose children according to their own entry of that vector,
+//! so that backups follow the max-n rule.
+
+use fastrand::Rng;
+
+use crate::game::Implicit;
+use crate::game::Player;
+use crate::game::PlayerCount;
+use crate::game::State;
+use crate::solver::IntegerUtility;
+use crate::solver::algorithm::rollout;
+
+/* DEFINITIONS */
+
+/// Parameters of a search.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Number of playouts performed before returning a result.
+    pub iterations: usize,
+
+    /// Weight given to the exploration term of the UCT formula. Since it is
+    /// added to average utilities, its scale should be comparable to that of
+    /// the game's utility values.
+    pub exploration: f64,
+}
+
+/// A node of the search tree, which is stored in an arena and refers to its
+/// children by their indices in it.
+struct Node<const N: PlayerCount, const B: usize> {
+    state: State<B>,
+    turn: Player,
+    children: Vec<usize>,
+    untried: Vec<State<B>>,
+    visits: u64,
+    total: [f64; N],
+}
+
+/// A search tree rooted at a single state.
+struct Tree<const N: PlayerCount, const B: usize> {
+    nodes: Vec<Node<N, B>>,
+}
+
+/* API */
+
+/// Returns the child of `state` in `game` that was visited the most by a
+/// search configured by `config`, or `None` if `state` is terminal.
+pub fn choose<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    config: &Config,
+    rng: &mut Rng,
+) -> Option<State<B>>
+where
+    G: Implicit<B> + IntegerUtility<N, B>,
+{
+    if game.sink(state) {
+        return None;
+    }
+
+    let tree = search(game, state, config, rng);
+    tree.nodes[0]
+        .children
+        .iter()
+        .map(|&c| &tree.nodes[c])
+        .max_by_key(|n| n.visits)
+        .map(|n| n.state)
+}
+
+/// Returns the average utility vector of the playouts that went through
+/// `state` in `game` during a search configured by `config`. This is exact if
+/// `state` is terminal.
+pub fn evaluate<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    config: &Config,
+    rng: &mut Rng,
+) -> [f64; N]
+where
+    G: Implicit<B> + IntegerUtility<N, B>,
+{
+    if game.sink(state) {
+        return game
+            .utility(state)
+            .map(|u| u as f64);
+    }
+
+    search(game, state, config, rng).nodes[0].mean()
+}
+
+/* HELPERS */
+
+/// Grows a search tree from `state` through `config.iterations` iterations of
+/// selection, expansion, simulation, and backpropagation.
+fn search<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    config: &Config,
+    rng: &mut Rng,
+) -> Tree<N, B>
+where
+    G: Implicit<B> + IntegerUtility<N, B>,
+{
+    let mut tree = Tree {
+        nodes: vec![Node::new(game, state)],
+    };
+
+    for _ in 0..config.iterations.max(1) {
+        let mut path = vec![0];
+        let mut curr = 0;
+        while tree.nodes[curr].untried.is_empty()
+            && !tree.nodes[curr]
+                .children
+                .is_empty()
+        {
+            curr = tree.select(curr, config.exploration);
+            path.push(curr);
+        }
+
+        if !tree.nodes[curr].untried.is_empty() {
+            let untried = &mut tree.nodes[curr].untried;
+            let child = untried.swap_remove(rng.usize(..untried.len()));
+            tree.nodes
+                .push(Node::new(game, child));
+            let index = tree.nodes.len() - 1;
+            tree.nodes[curr]
+                .children
+                .push(index);
+            path.push(index);
+            curr = index;
+        }
+
+        let utility = rollout::playout(game, tree.nodes[curr].state, rng);
+        for &i in &path {
+            let node = &mut tree.nodes[i];
+            node.visits += 1;
+            node.total
+                .iter_mut()
+                .zip(utility)
+                .for_each(|(t, u)| *t += u as f64);
+        }
+    }
+
+    tree
+}
+
+/* TREE IMPLEMENTATION */
+
+impl<const N: PlayerCount, const B: usize> Tree<N, B> {
+    /// Returns the index of the child of node `parent` which maximizes the UCT
+    /// score for the player to move at it.
+    fn select(&self, parent: usize, exploration: f64) -> usize {
+        let node = &self.nodes[parent];
+        let log = (node.visits as f64).ln();
+        let score = |i: usize| {
+            let child = &self.nodes[i];
+            let visits = child.visits as f64;
+            child.total[node.turn] / visits
+                + exploration * (log / visits).sqrt()
+        };
+
+        node.children
+            .iter()
+            .copied()
+            .max_by(|&a, &b| score(a).total_cmp(&score(b)))
+            .expect("Attempted to select a child of a leaf node.")
+    }
+}
+
+/* NODE IMPLEMENTATION */
+
+impl<const N: PlayerCount, const B: usize> Node<N, B> {
+    fn new<G>(game: &G, state: State<B>) -> Self
+    where
+        G: Implicit<B> + IntegerUtility<N, B>,
+    {
+        let untried =
+            if game.sink(state) { Vec::new() } else { game.adjacent(state) };
+
+        Self {
+            state,
+            turn: game.turn(state),
+            children: Vec::new(),
+            untried,
+            visits: 0,
+            total: [0.0; N],
+        }
+    }
+
+    /// Returns the average utility vector of the playouts through this node.
+    fn mean(&self) -> [f64; N] {
+        let visits = self.visits.max(1) as f64;
+        self.total.map(|t| t / visits)
+    }
+}
+
+/* AUXILIARY IMPLEMENTATIONS */
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            iterations: 1000,
+            exploration: std::f64::consts::SQRT_2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::game::Codec;
+    use crate::game::Variable;
+    use crate::game::mnk;
+    use crate::game::zero_by;
+
+    #[test]
+    fn search_finds_winning_moves() -> Result<()> {
+        let game = zero_by::Session::variant("2-10-1-2".into())?;
+        let config = Config::default();
+        let mut rng = Rng::with_seed(3);
+        for (state, best) in [("9-0", "7-1"), ("8-1", "7-0"), ("2-0", "1-1")] {
+            let state = game.decode(state.into())?;
+            let chosen = choose::<2, 8, _>(&game, state, &config, &mut rng);
+            assert_eq!(chosen, Some(game.decode(best.into())?));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn search_blocks_immediate_threats() -> Result<()> {
+        let game = mnk::Session::default();
+        let config = Config::default();
+        let mut rng = Rng::with_seed(5);
+        let state = game.decode("[[X, X, _], [O, _, _], [_, _, _]]".into())?;
+        let block = game.decode("[[X, X, O], [O, _, _], [_, _, _]]".into())?;
+        let chosen = choose::<2, 8, _>(&game, state, &config, &mut rng);
+        assert_eq!(chosen, Some(block));
+        Ok(())
+    }
+
+    #[test]
+    fn evaluation_is_exact_at_terminal_states() -> Result<()> {
+        let game = zero_by::Session::variant("3-4-1".into())?;
+        let state = game.decode("0-2".into())?;
+        let value = evaluate::<3, 8, _>(
+            &game,
+            state,
+            &Config::default(),
+            &mut Rng::with_seed(1),
+        );
+
+        let exact = IntegerUtility::<3>::utility(&game, state);
+        assert_eq!(value, exact.map(|u| u as f64));
+        Ok(())
+    }
+}
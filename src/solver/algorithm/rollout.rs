@@ -0,0 +1,84 @@
+//! # Rollout Estimation Module
+//!
+//! This module implements random playouts over acyclic games, which can be
+//! used to estimate the utility of states whose game is too large to solve.
+
+use fastrand::Rng;
+
+use crate::game::Implicit;
+use crate::game::PlayerCount;
+use crate::game::State;
+use crate::solver::IUtility;
+use crate::solver::IntegerUtility;
+
+/* ESTIMATORS */
+
+/// Plays `game` from `state` by choosing moves uniformly at random until a
+/// terminal state is reached, returning the utility vector of that state.
+///
+/// This assumes that `game` is acyclic, as otherwise it may never terminate.
+pub fn playout<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    rng: &mut Rng,
+) -> [IUtility; N]
+where
+    G: Implicit<B> + IntegerUtility<N, B>,
+{
+    let mut curr = state;
+    while !game.sink(curr) {
+        let children = game.adjacent(curr);
+        curr = children[rng.usize(..children.len())];
+    }
+    game.utility(curr)
+}
+
+/// Returns the average utility vector of `rollouts` random playouts of `game`
+/// from `state` (see [`playout`]). Returns all zeroes if `rollouts` is zero.
+pub fn estimate<const N: PlayerCount, const B: usize, G>(
+    game: &G,
+    state: State<B>,
+    rollouts: usize,
+    rng: &mut Rng,
+) -> [f64; N]
+where
+    G: Implicit<B> + IntegerUtility<N, B>,
+{
+    let mut total = [0.0; N];
+    for _ in 0..rollouts {
+        let utility = playout(game, state, rng);
+        total
+            .iter_mut()
+            .zip(utility)
+            .for_each(|(t, u)| *t += u as f64);
+    }
+
+    if rollouts > 0 {
+        total
+            .iter_mut()
+            .for_each(|t| *t /= rollouts as f64);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::game::Codec;
+    use crate::game::Variable;
+    use crate::game::zero_by;
+
+    #[test]
+    fn estimates_are_exact_at_forced_states() -> Result<()> {
+        let game = zero_by::Session::variant("2-3-1".into())?;
+        let mut rng = Rng::with_seed(7);
+        let state = game.decode("3-0".into())?;
+        let value = estimate::<2, 8, _>(&game, state, 16, &mut rng);
+        let exact = playout::<2, 8, _>(&game, state, &mut rng);
+        assert_eq!(value, exact.map(|u| u as f64));
+        Ok(())
+    }
+}
@@ -3,20 +3,36 @@
 //! Contains abstractions for handling database logic, including schema
 //! definitions and constructors.
 
+use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
+use fastrand::Rng;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::types::Value;
+use rusqlite::types::ValueRef;
 
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use crate::game::DEFAULT_STATE_BYTES;
 use crate::game::PlayerCount;
+use crate::game::State;
 use crate::solver::util::sqlize;
 use crate::util;
 
 /* DEFINITIONS */
 
+/// A table row, as a sequence of column names paired with their values in the
+/// order in which they appear in the table.
+pub type Record = Vec<(String, Value)>;
+
 /// A database column within a table schema, corresponding to one attribute.
 #[derive(Default, Clone)]
 pub struct Column {
     name: String,
     data: String,
+    nullable: bool,
 }
 
 /// Builder pattern for a database table schema, specifying and guaranteeing a
@@ -26,6 +42,8 @@ pub struct SchemaBuilder {
     columns: Vec<Column>,
     players: Option<PlayerCount>,
     key: Option<Column>,
+    estimates: bool,
+    prefix: String,
     table: String,
 }
 
@@ -48,6 +66,8 @@ impl SchemaBuilder {
             columns: Vec::new(),
             players: None,
             key: None,
+            estimates: false,
+            prefix: String::new(),
         }
     }
 
@@ -65,6 +85,28 @@ impl SchemaBuilder {
         self
     }
 
+    /// Inserts a new column into the table schema which may hold null values.
+    pub fn nullable(mut self, name: &str, data: &str) -> Self {
+        let mut column = Column::new(name, data);
+        column.nullable = true;
+        self.columns.push(column);
+        self
+    }
+
+    /// Declares that utility entries are estimates rather than exact values,
+    /// making them nullable real numbers instead of integers.
+    pub fn estimates(mut self) -> Self {
+        self.estimates = true;
+        self
+    }
+
+    /// Prepends `prefix` to the name of the table, so that tables of the same
+    /// name can be kept apart within one database.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
     /// Adds a column that will be marked as primary key.
     pub fn key(mut self, name: &str, data: &str) -> Self {
         self.key = Some(Column::new(name, data));
@@ -116,7 +158,7 @@ impl SchemaBuilder {
 
         Ok(Schema {
             columns: self.columns,
-            table: self.table,
+            table: sqlize(&format!("{}{}", self.prefix, self.table)),
             players,
             key,
         })
@@ -125,8 +167,12 @@ impl SchemaBuilder {
     /* UTILITY */
 
     fn utility_columns(&mut self, players: PlayerCount) -> Vec<Column> {
+        let data = if self.estimates { "REAL" } else { "INTEGER" };
         (0..players)
-            .map(|i| Column::new(&format!("utility_{}", i), "INTEGER"))
+            .map(|i| Column {
+                nullable: self.estimates,
+                ..Column::new(&format!("utility_{}", i), data)
+            })
             .collect::<Vec<Column>>()
     }
 }
@@ -171,25 +217,57 @@ impl Schema {
         format!("DROP TABLE IF EXISTS {};", self.table())
     }
 
+    /// Returns an SQL 'SELECT' query string with one placeholder for the key
+    /// of the row to retrieve from the schema's table, with all of its columns
+    /// (including those that may not be part of the schema).
+    pub fn select_record_query(&self) -> String {
+        format!(
+            "SELECT * FROM {} WHERE {} = ?",
+            self.table(),
+            self.key()
+        )
+    }
+
+    /// Returns an SQL 'SELECT' query string that retrieves every row of the
+    /// schema's table, with all of its columns.
+    pub fn select_all_query(&self) -> String {
+        format!("SELECT * FROM {}", self.table())
+    }
+
     /// Returns the table row index where utility entries start in the schema.
     pub fn utility_index(&self) -> usize {
         self.len() - self.players
     }
 
-    /* UTILS */
+    /// Returns the names and SQL datatypes of the columns in this schema,
+    /// starting with its primary key.
+    pub fn columns(&self) -> Vec<(&str, &str)> {
+        let mut columns = vec![(self.key.name(), self.key.datatype())];
+        columns.extend(
+            self.columns
+                .iter()
+                .map(|c| (c.name(), c.datatype())),
+        );
 
-    fn len(&self) -> usize {
-        self.columns.len() + 1
+        columns
     }
 
-    fn table(&self) -> &str {
+    /// Returns the name of the table this schema belongs to.
+    pub fn table(&self) -> &str {
         &self.table
     }
 
-    fn key(&self) -> &str {
+    /// Returns the name of the primary key column of this schema.
+    pub fn key(&self) -> &str {
         self.key.name()
     }
 
+    /* UTILS */
+
+    fn len(&self) -> usize {
+        self.columns.len() + 1
+    }
+
     fn colnames(&self) -> String {
         let mut cols = Vec::new();
         cols.push(format!("\"{}\"", self.key.name()));
@@ -227,7 +305,11 @@ impl Schema {
         ));
 
         self.columns.iter().for_each(|c| {
-            fields.push(format!("{} {} NOT NULL", c.name(), c.datatype()))
+            if c.nullable {
+                fields.push(format!("{} {}", c.name(), c.datatype()))
+            } else {
+                fields.push(format!("{} {} NOT NULL", c.name(), c.datatype()))
+            }
         });
 
         fields.join(", ")
@@ -239,6 +321,7 @@ impl Column {
         Self {
             name: sqlize(name),
             data: sqlize(data),
+            nullable: false,
         }
     }
 
@@ -251,11 +334,208 @@ impl Column {
     }
 }
 
+/* CONNECTION UTILITIES */
+
+/// Returns true if a table with the name `table` exists in the database that
+/// `conn` is connected to.
+pub fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    let found = conn
+        .query_row(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [table],
+            |_| Ok(()),
+        )
+        .optional()
+        .context("Failed to query database schema.")?;
+
+    Ok(found.is_some())
+}
+
+/// Returns the names and declared SQL datatypes of the columns of `table` in
+/// the database that `conn` is connected to, in the order they appear in it.
+pub fn columns(
+    conn: &Connection,
+    table: &str,
+) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn
+        .prepare("SELECT name, type FROM pragma_table_info(?)")
+        .context("Failed to prepare table information query.")?;
+
+    let columns = stmt
+        .query_map([table], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(String, String)>, _>>()
+        .context("Failed to query table information.")?;
+
+    Ok(columns)
+}
+
+/// Retrieves the full row of the table in `schema` whose primary key is that
+/// of `state` through `conn`, or `None` if there is no such row.
+pub fn record<const B: usize>(
+    conn: &Connection,
+    schema: &Schema,
+    state: &State<B>,
+) -> Result<Option<Record>> {
+    let mut stmt = conn
+        .prepare_cached(&schema.select_record_query())
+        .context("Failed to prepare record query.")?;
+
+    let names: Vec<String> = stmt
+        .column_names()
+        .iter()
+        .map(|&n| n.to_owned())
+        .collect();
+
+    stmt.query_row([key(state)], |row| {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| Ok((n.clone(), row.get::<_, Value>(i)?)))
+            .collect()
+    })
+    .optional()
+    .context("Failed to query record.")
+}
+
+/// Returns up to `count` distinct states chosen uniformly at random through
+/// `rng` from the primary keys of the rows of the table in `schema` which
+/// satisfy the SQL condition `filter` (or from all rows, if not provided).
+/// Only the chosen keys are kept in memory, and the same states are chosen
+/// from the same table given an equally seeded `rng`.
+pub fn sample<const B: usize>(
+    conn: &Connection,
+    schema: &Schema,
+    filter: Option<&str>,
+    count: usize,
+    rng: &mut Rng,
+) -> Result<Vec<State<B>>> {
+    let filter = filter
+        .map(|f| format!(" WHERE {f}"))
+        .unwrap_or_default();
+
+    let total: usize = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM {}{filter}", schema.table()),
+            [],
+            |row| row.get(0),
+        )
+        .context("Failed to count rows of table.")?;
+
+    // Floyd's algorithm picks the offsets of distinct rows without drawing
+    // more random numbers than the number of rows chosen.
+    let mut offsets = BTreeSet::new();
+    for bound in total - count.min(total)..total {
+        let offset = rng.usize(..=bound);
+        if !offsets.insert(offset) {
+            offsets.insert(bound);
+        }
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT \"{0}\" FROM {1}{filter} ORDER BY \"{0}\"",
+        schema.key(),
+        schema.table(),
+    ))?;
+
+    let mut rows = stmt.query([])?;
+    let mut states = Vec::with_capacity(offsets.len());
+    let mut offset = 0;
+    for target in offsets {
+        let row = loop {
+            let row = rows
+                .next()?
+                .context("Table changed while sampling its rows.")?;
+
+            offset += 1;
+            if offset > target {
+                break row;
+            }
+        };
+        states.push(state(row.get_ref(0)?)?);
+    }
+
+    rng.shuffle(&mut states);
+    Ok(states)
+}
+
+/// Returns the integer stored under `column` in `record`, if there is one.
+pub fn integer(record: &Record, column: &str) -> Option<i64> {
+    record
+        .iter()
+        .find(|(n, _)| n == column)
+        .and_then(|(_, v)| match v {
+            Value::Integer(i) => Some(*i),
+            _ => None,
+        })
+}
+
+/* KEY UTILITIES */
+
+/// Returns the SQL datatype of the primary keys of tables holding states of
+/// `B` bytes, which are stored as integers when they fit in one, and as blobs
+/// otherwise.
+pub const fn key_type<const B: usize>() -> &'static str {
+    if B == DEFAULT_STATE_BYTES { "INTEGER" } else { "BLOB" }
+}
+
+/// Returns the primary key under which `state` is stored. See [`key_type`].
+pub fn key<const B: usize>(state: &State<B>) -> Value {
+    if let Ok(bytes) = <[u8; 8]>::try_from(&state[..]) {
+        Value::Integer(i64::from_be_bytes(bytes))
+    } else {
+        Value::Blob(state.to_vec())
+    }
+}
+
+/// Transforms a stored primary key back into the state it was derived from.
+///
+/// # Errors
+///
+/// Fails if `value` is not the key of a state of `B` bytes.
+pub fn state<const B: usize>(value: ValueRef) -> Result<State<B>> {
+    let state = match value {
+        ValueRef::Integer(key) => State::<B>::try_from(&key.to_be_bytes()[..]),
+        ValueRef::Blob(key) => State::<B>::try_from(key),
+        _ => bail!("Found a state key which is neither an integer nor a blob."),
+    };
+
+    state.context(format!(
+        "Found a state key which is not {B} bytes long in solution table."
+    ))
+}
+
+/// Compares states in the order in which their primary keys are sorted by
+/// the database.
+pub fn order<const B: usize>(a: &State<B>, b: &State<B>) -> Ordering {
+    match (key(a), key(b)) {
+        (Value::Integer(a), Value::Integer(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
 
+    #[test]
+    fn keys_round_trip_and_sort_like_the_database() -> Result<()> {
+        let narrow: State = (-2_i64).to_be_bytes();
+        assert_eq!(key(&narrow), Value::Integer(-2));
+        assert_eq!(state::<8>(ValueRef::Integer(-2))?, narrow);
+        assert_eq!(
+            order(&narrow, &1_i64.to_be_bytes()),
+            Ordering::Less
+        );
+
+        let wide: State<9> = [1; 9];
+        assert_eq!(key(&wide), Value::Blob(vec![1; 9]));
+        assert_eq!(state::<9>(ValueRef::Blob(&[1; 9]))?, wide);
+        assert!(state::<9>(ValueRef::Integer(1)).is_err());
+        assert!(state::<8>(ValueRef::Blob(&[1; 9])).is_err());
+        Ok(())
+    }
+
     #[test]
     fn builder_fails_if_columns_repeat() -> Result<()> {
         let b1 = SchemaBuilder::new("example")
@@ -283,4 +563,23 @@ mod tests {
         assert!(key_missing.is_err());
         Ok(())
     }
+
+    #[test]
+    fn table_names_are_prefixed_only_when_requested() -> Result<()> {
+        let builder = |table| {
+            SchemaBuilder::new(table)
+                .players(2)
+                .key("state", "INTEGER")
+        };
+
+        assert_eq!(builder("example").build()?.table(), "example");
+        assert_eq!(
+            builder("example")
+                .prefix("x_")
+                .build()?
+                .table(),
+            "x_example"
+        );
+        Ok(())
+    }
 }
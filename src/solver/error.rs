@@ -13,14 +13,25 @@ use std::{error::Error, fmt};
 pub enum SolverError {
     /// An error to indicate that the assumptions of a solving algorithm were
     /// detectably violated during execution.
-    SolverViolation { name: String, hint: String },
+    SolverViolation {
+        /// Name of the solving algorithm.
+        name: String,
+
+        /// Explanation of the violated assumption.
+        hint: String,
+    },
 
     /// An error to indicate that there was an attempt to translate one measure
     /// into another incompatible measure. Provides hints about the input type,
     /// output type, and the reason behind the incompatibility.
     InvalidConversion {
+        /// Name of the measure that was to be produced.
         output_t: String,
+
+        /// Name of the measure that was provided.
         input_t: String,
+
+        /// Explanation of the incompatibility.
         hint: String,
     },
 }
@@ -0,0 +1,416 @@
+//! # Solution Feature Module
+//!
+//! This module provides features of game states which are derived from the
+//! solution of a game rather than from its rules, such as the distribution of
+//! the values of a state's children. They are computed by combining a game's
+//! [`Implicit`] structure with the entries of its solution table, so they are
+//! available for every solved game.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::types::Value;
+
+use crate::game::DEFAULT_STATE_BYTES;
+use crate::game::Implicit;
+use crate::game::Player;
+use crate::game::State;
+use crate::solver::IUtility;
+use crate::solver::Remoteness;
+use crate::solver::db;
+use crate::solver::db::Schema;
+
+/* DEFINITIONS */
+
+/// The solved attributes of a single state, as stored in a solution table.
+pub struct Outcome {
+    /// Number of moves to the end of the game under perfect play.
+    pub remoteness: Remoteness,
+
+    /// Utility of the state for each player under perfect play.
+    pub utility: Vec<IUtility>,
+
+    /// Player whose turn it is at the state.
+    pub player: Player,
+}
+
+/// Read access to the entries of a solution table.
+pub struct Table<'a> {
+    conn: &'a Connection,
+    schema: &'a Schema,
+}
+
+/// A named, typed attribute of game states which is computed from the solved
+/// attributes of a state and of the states reachable from it. This is the
+/// solution-derived counterpart of [`crate::game::Feature`].
+pub struct SolutionFeature<G, const B: usize = DEFAULT_STATE_BYTES> {
+    /// Name of the feature, which is used as its column name in solution
+    /// tables and to select it through the CLI.
+    pub name: &'static str,
+
+    /// SQL datatype of the values produced by [`SolutionFeature::extract`].
+    pub datatype: &'static str,
+
+    /// Computes the value of this feature for a state of `G`.
+    pub extract: fn(&G, &Table, State<B>) -> Result<Value>,
+}
+
+/* API */
+
+/// Returns all features derived from solutions which are available for `G`.
+///
+/// All of them are computed from the perspective of the player whose turn it
+/// is at the state in question, where a child is winning, tying, or losing
+/// according to the sign of that player's utility at it:
+///
+/// * `winning_moves`: Number of moves leading to a winning child.
+/// * `tying_moves`: Number of moves leading to a tying child.
+/// * `losing_moves`: Number of moves leading to a losing child.
+/// * `remoteness_spread`: Difference between the largest and smallest
+///   remoteness among children, or 0 at terminal states.
+/// * `fragile`: 1 if exactly one of several moves is not losing, else 0.
+/// * `blunder_distance`: Number of moves played under perfect play before
+///   reaching a state where the player to move has a move that lowers their
+///   utility, or -1 if no such state is reached.
+pub fn features<const B: usize, G>() -> Vec<SolutionFeature<G, B>>
+where
+    G: Implicit<B>,
+{
+    vec![
+        SolutionFeature {
+            name: "winning_moves",
+            datatype: "INTEGER",
+            extract: |g, t, s| count(g, t, s, |u| u > 0),
+        },
+        SolutionFeature {
+            name: "tying_moves",
+            datatype: "INTEGER",
+            extract: |g, t, s| count(g, t, s, |u| u == 0),
+        },
+        SolutionFeature {
+            name: "losing_moves",
+            datatype: "INTEGER",
+            extract: |g, t, s| count(g, t, s, |u| u < 0),
+        },
+        SolutionFeature {
+            name: "remoteness_spread",
+            datatype: "INTEGER",
+            extract: remoteness_spread,
+        },
+        SolutionFeature {
+            name: "fragile",
+            datatype: "INTEGER",
+            extract: fragile,
+        },
+        SolutionFeature {
+            name: "blunder_distance",
+            datatype: "INTEGER",
+            extract: blunder_distance,
+        },
+    ]
+}
+
+/* FEATURE CALCULATORS */
+
+/// Returns the number of children of `state` at which the utility of the
+/// player to move at `state` satisfies `pred`.
+fn count<const B: usize, G>(
+    game: &G,
+    table: &Table,
+    state: State<B>,
+    pred: fn(IUtility) -> bool,
+) -> Result<Value>
+where
+    G: Implicit<B>,
+{
+    let turn = table.outcome(state)?.player;
+    let count = table
+        .children(game, state)?
+        .iter()
+        .filter(|c| pred(c.utility[turn]))
+        .count();
+
+    Ok(Value::Integer(count as i64))
+}
+
+fn remoteness_spread<const B: usize, G>(
+    game: &G,
+    table: &Table,
+    state: State<B>,
+) -> Result<Value>
+where
+    G: Implicit<B>,
+{
+    let children = table.children(game, state)?;
+    let max = children
+        .iter()
+        .map(|c| c.remoteness)
+        .max();
+    let min = children
+        .iter()
+        .map(|c| c.remoteness)
+        .min();
+    match (max, min) {
+        (Some(max), Some(min)) => Ok(Value::Integer((max - min) as i64)),
+        _ => Ok(Value::Integer(0)),
+    }
+}
+
+fn fragile<const B: usize, G>(
+    game: &G,
+    table: &Table,
+    state: State<B>,
+) -> Result<Value>
+where
+    G: Implicit<B>,
+{
+    let turn = table.outcome(state)?.player;
+    let children = table.children(game, state)?;
+    let safe = children
+        .iter()
+        .filter(|c| c.utility[turn] >= 0)
+        .count();
+
+    let fragile = safe == 1 && children.len() > 1;
+    Ok(Value::Integer(fragile as i64))
+}
+
+fn blunder_distance<const B: usize, G>(
+    game: &G,
+    table: &Table,
+    state: State<B>,
+) -> Result<Value>
+where
+    G: Implicit<B>,
+{
+    let mut distance = 0;
+    let mut current = state;
+    loop {
+        let outcome = table.outcome(current)?;
+        let turn = outcome.player;
+        let children = table.children(game, current)?;
+        if children
+            .iter()
+            .any(|c| c.utility[turn] < outcome.utility[turn])
+        {
+            return Ok(Value::Integer(distance));
+        }
+
+        let adjacent = if game.sink(current) {
+            Vec::new()
+        } else {
+            game.adjacent(current)
+        };
+
+        if let Some((child, _)) = table.best(turn, &adjacent)? {
+            current = child;
+            distance += 1;
+        } else {
+            return Ok(Value::Integer(-1));
+        }
+    }
+}
+
+/* TABLE IMPLEMENTATION */
+
+impl<'a> Table<'a> {
+    /// Returns read access to the solution table described by `schema`
+    /// through `conn`.
+    pub fn new(conn: &'a Connection, schema: &'a Schema) -> Self {
+        Self { conn, schema }
+    }
+
+    /// Returns the solved attributes of `state`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `state` is not present in the solution table.
+    pub fn outcome<const B: usize>(&self, state: State<B>) -> Result<Outcome> {
+        if let Some(outcome) = self.lookup(state)? {
+            Ok(outcome)
+        } else {
+            bail!(
+                "State {:?} is not present in the solution table.",
+                db::key(&state)
+            )
+        }
+    }
+
+    /// Returns the solved attributes of `state`, or `None` if it is not
+    /// present in the solution table.
+    pub fn lookup<const B: usize>(
+        &self,
+        state: State<B>,
+    ) -> Result<Option<Outcome>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached(&self.schema.select_query())
+            .context("Failed to prepare solution query.")?;
+
+        let start = self.schema.utility_index();
+        stmt.query_row([db::key(&state)], |row| {
+            let utility = (start..row.as_ref().column_count())
+                .map(|i| row.get(i))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Outcome {
+                remoteness: row.get("remoteness")?,
+                player: row.get::<_, i64>("player")? as Player,
+                utility,
+            })
+        })
+        .optional()
+        .context("Failed to query solution table.")
+    }
+
+    /// Returns the solved attributes of the children of `state` in `game`,
+    /// which has none if it is terminal.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any child of `state` is not present in the solution table.
+    pub fn children<const B: usize, G>(
+        &self,
+        game: &G,
+        state: State<B>,
+    ) -> Result<Vec<Outcome>>
+    where
+        G: Implicit<B>,
+    {
+        Ok(self
+            .successors(game, state)?
+            .into_iter()
+            .map(|(_, o)| o)
+            .collect())
+    }
+
+    /// Returns the state in `children` that the player `turn` moves to under
+    /// perfect play along with its solved attributes, or `None` if there are
+    /// no `children`. This is the child with the highest utility for `turn`,
+    /// breaking ties by lowest remoteness and then by order in `children`,
+    /// which is the policy the solvers use to assign values to states.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any state in `children` is not present in the solution table.
+    pub fn best<const B: usize>(
+        &self,
+        turn: Player,
+        children: &[State<B>],
+    ) -> Result<Option<(State<B>, Outcome)>> {
+        let mut best: Option<(State<B>, Outcome)> = None;
+        for &child in children {
+            let next = self.outcome(child)?;
+            let better = best.as_ref().is_none_or(|(_, b)| {
+                next.utility[turn] > b.utility[turn]
+                    || (next.utility[turn] == b.utility[turn]
+                        && next.remoteness < b.remoteness)
+            });
+
+            if better {
+                best = Some((child, next));
+            }
+        }
+
+        Ok(best)
+    }
+
+    /// Returns the children of `state` in `game` along with their solved
+    /// attributes, or nothing if `state` is terminal.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any child of `state` is not present in the solution table.
+    pub fn successors<const B: usize, G>(
+        &self,
+        game: &G,
+        state: State<B>,
+    ) -> Result<Vec<(State<B>, Outcome)>>
+    where
+        G: Implicit<B>,
+    {
+        if game.sink(state) {
+            return Ok(Vec::new());
+        }
+
+        game.adjacent(state)
+            .into_iter()
+            .map(|c| Ok((c, self.outcome(c)?)))
+            .collect()
+    }
+}
+
+/* AUXILIARY IMPLEMENTATIONS */
+
+impl<G, const B: usize> Clone for SolutionFeature<G, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G, const B: usize> Copy for SolutionFeature<G, B> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::Codec;
+    use crate::game::zero_by;
+    use crate::test;
+
+    /// Returns the value of the feature `name` at the zero-by state encoded
+    /// by `state`, in a table containing the solution to 2-player zero-by
+    /// from 3 by 1 or 2.
+    fn feature(name: &str, state: &str) -> Result<Value> {
+        let (conn, game, schema) =
+            test::solved_zero_by("solution_features_test")?;
+
+        let table = Table::new(&conn, &schema);
+        let feature = features::<8, zero_by::Session>()
+            .into_iter()
+            .find(|f| f.name == name)
+            .context("Feature not found.")?;
+
+        (feature.extract)(&game, &table, game.decode(state.into())?)
+    }
+
+    #[test]
+    fn child_values_are_counted_for_player_to_move() -> Result<()> {
+        assert_eq!(
+            feature("winning_moves", "3-0")?,
+            Value::Integer(1)
+        );
+        assert_eq!(feature("losing_moves", "3-0")?, Value::Integer(1));
+        assert_eq!(feature("tying_moves", "3-0")?, Value::Integer(0));
+        assert_eq!(
+            feature("remoteness_spread", "3-0")?,
+            Value::Integer(1)
+        );
+        assert_eq!(feature("fragile", "3-0")?, Value::Integer(1));
+        assert_eq!(feature("fragile", "1-1")?, Value::Integer(0));
+        Ok(())
+    }
+
+    #[test]
+    fn blunder_distance_follows_perfect_play() -> Result<()> {
+        assert_eq!(
+            feature("blunder_distance", "3-0")?,
+            Value::Integer(0)
+        );
+        assert_eq!(
+            feature("blunder_distance", "2-1")?,
+            Value::Integer(0)
+        );
+        assert_eq!(
+            feature("blunder_distance", "1-1")?,
+            Value::Integer(-1)
+        );
+        assert_eq!(
+            feature("blunder_distance", "0-0")?,
+            Value::Integer(-1)
+        );
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ use crate::game::Player;
 use crate::game::PlayerCount;
 use crate::game::State;
 use crate::interface::IOMode;
+use crate::solver::db::Schema;
 
 /* UTILITY MODULES */
 
@@ -22,8 +23,12 @@ pub mod error;
 /* MODULES */
 
 pub mod db;
+pub mod features;
+/// Solving and search algorithms over the interfaces of this module.
 pub mod algorithm {
     pub mod acyclic;
+    pub mod mcts;
+    pub mod rollout;
 }
 
 /* TYPES */
@@ -46,29 +51,44 @@ pub type IUtility = i64;
 #[derive(Clone, Copy)]
 #[repr(i8)]
 pub enum SUtility {
+    /// The player is worse off for having played the game.
     Lose = -1,
+
+    /// The player is neither better nor worse off.
     Tie = 0,
+
+    /// The player is better off for having played the game.
     Win = 1,
 }
 
 /* DEFINITIONS */
 
 /// Values that solving algorithms calculate for each state within a game.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Solution<const N: PlayerCount> {
+    /// Number of moves to the end of the game under perfect play.
     pub remoteness: Remoteness,
+
+    /// Utility of the state for each player under perfect play.
     pub utility: [IUtility; N],
+
+    /// Player whose turn it is at the state.
     pub player: Player,
 }
 
 /// SQL query strings to be prepared into pre-compiled statements.
 pub struct Queries {
+    /// Query inserting or replacing the solution of a state.
     pub insert: String,
+
+    /// Query selecting the solution of a state.
     pub select: String,
 }
 
 /* STRUCTURAL INTERFACES */
 
+/// Provides the turn structure of a game with `N` players, which solving
+/// algorithms rely on to determine who is choosing at each state.
 pub trait Game<const N: PlayerCount, const B: usize = DBYTES> {
     /// Returns the player `i` whose turn it is at the given `state`.
     ///
@@ -87,6 +107,8 @@ pub trait Game<const N: PlayerCount, const B: usize = DBYTES> {
 
 /* UTILITY MEASURE INTERFACES */
 
+/// Provides integer utility vectors at the terminal states of a game with `N`
+/// players, which is the most general utility structure solvers work with.
 pub trait IntegerUtility<const N: PlayerCount, const B: usize = DBYTES>
 where
     Self: Game<N, B>,
@@ -108,6 +130,8 @@ where
     fn utility(&self, state: State<B>) -> [IUtility; N];
 }
 
+/// Provides utility vectors made up of wins, ties, and losses at the terminal
+/// states of a game with `N` players.
 pub trait SimpleUtility<const N: PlayerCount, const B: usize = DBYTES>
 where
     Self: Game<N, B>,
@@ -133,6 +157,8 @@ where
 
 /* UTILITY STRUCTURE INTERFACES */
 
+/// Provides the outcome of terminal states of a two-player zero-sum game for
+/// the player in turn.
 pub trait ClassicGame<const B: usize = DBYTES>
 where
     Self: Game<2, B>,
@@ -162,6 +188,7 @@ where
     fn utility(&self, state: State<B>) -> SUtility;
 }
 
+/// Provides the outcome of terminal states of a single-player game.
 pub trait ClassicPuzzle<const B: usize = DBYTES>
 where
     Self: Game<1, B>,
@@ -193,6 +220,8 @@ where
 
 /* PERSISTENCE INTERFACES */
 
+/// Provides storage for the solutions that solving algorithms compute for the
+/// states of a game with `N` players.
 #[allow(async_fn_in_trait)]
 pub trait Persistent<const N: PlayerCount, const B: usize = DBYTES> {
     /// Stores `info` under the key `state`, replacing an existing entry.
@@ -238,6 +267,40 @@ pub trait Persistent<const N: PlayerCount, const B: usize = DBYTES> {
     ) -> Result<Queries>;
 }
 
+/// Provides the schema of the table where the solutions of a game session are
+/// persisted, which interfaces rely on to read them back.
+pub trait Tabular {
+    /// Returns the schema of the table holding this session's solutions.
+    fn schema(&self) -> &Schema;
+}
+
+/* DISPATCH INTERFACES */
+
+/// Declares [`Solvable`] over each of the player counts in `$count`.
+macro_rules! solvable {
+    ($($count:literal)*) => {
+        /// Provides the utility and persistence interfaces that solving
+        /// algorithms rely on for every player count up to [`MAX_PLAYERS`], so
+        /// that routines generic over a player count can be called through
+        /// [`players!`](crate::players) on sessions whose player count is only
+        /// known at runtime. This is implemented for all games that provide
+        /// these interfaces generically over their player count.
+        ///
+        /// [`MAX_PLAYERS`]: crate::game::MAX_PLAYERS
+        pub trait Solvable<const B: usize = DBYTES>:
+            $(IntegerUtility<$count, B> + Persistent<$count, B> +)* Sized
+        {
+        }
+
+        impl<const B: usize, G> Solvable<B> for G where
+            G: $(IntegerUtility<$count, B> + Persistent<$count, B> +)* Sized
+        {
+        }
+    };
+}
+
+solvable!(1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16);
+
 /* BLANKET IMPLEMENTATIONS */
 
 // All N-player simple-utility games are also N-player integer-utility games.
@@ -8,6 +8,7 @@ use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
 use rusqlite::Connection;
+use rusqlite::params;
 use strum_macros::Display;
 
 use std::env;
@@ -15,6 +16,12 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::RwLock;
 
+use crate::game::Codec;
+use crate::game::Variable;
+use crate::game::zero_by;
+use crate::solver::db::Schema;
+use crate::solver::db::SchemaBuilder;
+
 /* CONSTANTS */
 
 /// Global lock for creating development data directories. Since `cargo test`
@@ -35,6 +42,7 @@ const DEV_DIRECTORY: &str = "dev";
 #[strum(serialize_all = "kebab-case")]
 pub enum DevelopmentData {
     Visuals,
+    Files,
 }
 
 /// Specifies the level of side effects to generate during testing. This
@@ -74,6 +82,40 @@ pub fn database() -> Result<Connection> {
     Ok(db)
 }
 
+/// Returns a connection to the test database where the table `name` contains
+/// the solution to 2-player zero-by from 3 by 1 or 2, along with the game
+/// session and schema that produced it.
+pub fn solved_zero_by(
+    name: &str,
+) -> Result<(Connection, zero_by::Session, Schema)> {
+    let conn = database()?;
+    let game = zero_by::Session::variant("2-3-1-2".into())?;
+    let schema = SchemaBuilder::new(name)
+        .players(2)
+        .key("state", "INTEGER")
+        .column("remoteness", "INTEGER")
+        .column("player", "INTEGER")
+        .build()?;
+
+    conn.execute(&schema.create_table_query(), [])?;
+    for (state, remoteness, player, utility) in [
+        ("3-0", 2, 0, [1, -1]),
+        ("2-1", 2, 1, [-1, 1]),
+        ("1-1", 1, 1, [1, -1]),
+        ("1-0", 1, 0, [-1, 1]),
+        ("0-0", 0, 0, [1, -1]),
+        ("0-1", 0, 1, [-1, 1]),
+    ] {
+        let key = i64::from_be_bytes(game.decode(state.into())?);
+        conn.execute(
+            &schema.insert_query(),
+            params![key, remoteness, player, utility[0], utility[1]],
+        )?;
+    }
+
+    Ok((conn, game, schema))
+}
+
 /// Returns the testing side effects setting as obtained from the `TEST_SETTING`
 /// environment variable.
 pub fn test_setting() -> Result<TestSetting> {
@@ -54,10 +54,50 @@ macro_rules! node {
     };
 }
 
+/// Evaluates `$body` with the constant `$n` set to the player count `$count`,
+/// which is only known at runtime. This allows calling routines generic over
+/// a constant player count from game sessions whose player count depends on
+/// their variant. Evaluates to an error if `$count` is zero or greater than
+/// [`MAX_PLAYERS`](crate::game::MAX_PLAYERS), so `$body` must evaluate to an
+/// [`anyhow::Result`].
+///
+/// # Example
+///
+/// ```ignore
+/// // Solves a game with any number of players.
+/// players!(self.players, N => acyclic::solve::<N, 8, _>(self, mode))
+/// ```
+#[macro_export]
+macro_rules! players {
+    ($count:expr, $n:ident => $body:expr) => {
+        $crate::players!(
+            @arms $count, $n => $body;
+            1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16
+        )
+    };
+    (@arms $count:expr, $n:ident => $body:expr; $($i:literal)*) => {
+        match $count {
+            $($i => {
+                const $n: $crate::game::PlayerCount = $i;
+                $body
+            })*
+            count => Err($crate::anyhow::anyhow!(
+                "Games with {} players are not supported, as the number of \
+                players must be between 1 and {}.",
+                count,
+                $crate::game::MAX_PLAYERS,
+            )),
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
 
+    use anyhow::Result;
+
     use super::*;
+    use crate::game::MAX_PLAYERS;
 
     #[test]
     fn minimum_bits_for_unsigned_integer() {
@@ -73,4 +113,17 @@ mod tests {
         assert_eq!(min_ubits(0x0000_0000_F020_0DE0), 32);
         assert_eq!(min_ubits(0x0000_0000_0000_FDE0), 16);
     }
+
+    #[test]
+    fn player_counts_are_dispatched_up_to_maximum() -> Result<()> {
+        for count in 1..=MAX_PLAYERS {
+            let length =
+                players!(count, N => Ok::<_, anyhow::Error>([0; N].len()));
+            assert_eq!(length?, count);
+        }
+
+        assert!(players!(0, N => Ok(N)).is_err());
+        assert!(players!(MAX_PLAYERS + 1, N => Ok(N)).is_err());
+        Ok(())
+    }
 }